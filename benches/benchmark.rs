@@ -1,8 +1,9 @@
-use criterion::{Criterion, criterion_group, criterion_main};
+use criterion::{BenchmarkId, Criterion};
 use kty::cli::{ArgsOptions, DictionaryType, MainArgs, MainLangs, PathManager};
 use kty::lang::{EditionLang, Lang};
-use kty::{DMain, make_dict_simple};
+use kty::{DMain, PipelineTimings, make_dict_simple, make_dict_timed, stream_dict};
 use std::path::Path;
+use std::time::Duration;
 
 const BENCH_FIXTURES_DIR_100: &str = "benches/fixtures";
 
@@ -18,43 +19,205 @@ fn fixture_options(fixture_dir: &Path) -> ArgsOptions {
     }
 }
 
-fn fixture_main_args(
+/// One row of the benchmark table.
+///
+/// A scenario is an edition build with a `--first` cap and optional tweaks to the options; both
+/// monolingual (`source == target`) and bilingual (`source != target`) cases fit the same shape.
+/// Adding a case is a data edit to [`SCENARIOS`] rather than a new `criterion_group!` entry.
+struct BenchScenario {
+    name: &'static str,
     edition: EditionLang,
     source: Lang,
     target: EditionLang,
-    fixture_path: &Path,
-) -> MainArgs {
-    MainArgs {
-        langs: MainLangs {
-            edition,
-            source,
-            target,
-        },
-        options: fixture_options(fixture_path),
-        ..Default::default()
+    first: i32,
+    /// Number of entries the fixture yields for this scenario, used to derive throughput.
+    entries: u64,
+    options_overrides: fn(&mut ArgsOptions),
+}
+
+fn no_overrides(_: &mut ArgsOptions) {}
+
+const SCENARIOS: &[BenchScenario] = &[
+    BenchScenario {
+        name: "el_el",
+        edition: EditionLang::El,
+        source: Lang::El,
+        target: EditionLang::El,
+        first: -1,
+        entries: 100,
+        options_overrides: no_overrides,
+    },
+    BenchScenario {
+        name: "de_de",
+        edition: EditionLang::De,
+        source: Lang::De,
+        target: EditionLang::De,
+        first: -1,
+        entries: 100,
+        options_overrides: no_overrides,
+    },
+    // Bilingual: an English edition read for Ancient Greek, capped at the first 50 entries.
+    BenchScenario {
+        name: "grc_en",
+        edition: EditionLang::En,
+        source: Lang::Grc,
+        target: EditionLang::En,
+        first: 50,
+        entries: 50,
+        options_overrides: no_overrides,
+    },
+];
+
+impl BenchScenario {
+    fn args(&self, fixture_path: &Path) -> MainArgs {
+        let mut options = fixture_options(fixture_path);
+        options.first = self.first;
+        (self.options_overrides)(&mut options);
+
+        MainArgs {
+            langs: MainLangs {
+                edition: self.edition,
+                source: self.source,
+                target: self.target,
+            },
+            options,
+            ..Default::default()
+        }
+    }
+}
+
+fn bench_main_dict(c: &mut Criterion) {
+    let fixture_path = Path::new(BENCH_FIXTURES_DIR_100);
+    let mut group = c.benchmark_group("main_dict");
+
+    for scenario in SCENARIOS {
+        let args = scenario.args(fixture_path);
+        let pm = PathManager::new(DictionaryType::Main, &args);
+
+        group.bench_with_input(
+            BenchmarkId::new(scenario.name, scenario.first),
+            scenario,
+            |b, _| b.iter(|| make_dict_simple(DMain, &args.options, &pm)),
+        );
+
+        std::fs::remove_dir_all(pm.dir_dicts()).unwrap();
     }
+
+    group.finish();
 }
 
-fn bench_monolingual(c: &mut Criterion, edition: EditionLang, label: &str) {
+/// Time to first entry: pull a single item off the streaming builder and stop.
+///
+/// This isolates startup/parse latency from total build throughput measured by [`bench_main_dict`].
+fn bench_time_to_first_entry(c: &mut Criterion) {
     let fixture_path = Path::new(BENCH_FIXTURES_DIR_100);
-    let args = fixture_main_args(edition, edition.into(), edition, fixture_path);
+    let mut group = c.benchmark_group("time_to_first_entry");
+
+    for scenario in SCENARIOS {
+        let args = scenario.args(fixture_path);
+        let pm = PathManager::new(DictionaryType::Main, &args);
+
+        group.bench_with_input(
+            BenchmarkId::new(scenario.name, scenario.first),
+            scenario,
+            |b, _| {
+                b.iter(|| {
+                    let mut stream = stream_dict(DMain, &args.options, &pm).unwrap();
+                    stream.next()
+                });
+            },
+        );
+
+        std::fs::remove_dir_all(pm.dir_dicts()).unwrap();
+    }
+
+    group.finish();
+}
+
+/// Report each build stage as its own `criterion` function, so a regression localizes to a stage.
+fn bench_pipeline_stages(c: &mut Criterion) {
+    let fixture_path = Path::new(BENCH_FIXTURES_DIR_100);
+    let mut group = c.benchmark_group("pipeline_stages");
+
+    let scenario = &SCENARIOS[0];
+    let args = scenario.args(fixture_path);
     let pm = PathManager::new(DictionaryType::Main, &args);
 
-    c.bench_function(label, |b| {
-        b.iter(|| make_dict_simple(DMain, &args.options, &pm))
-    });
+    let stages: &[(&str, fn(&PipelineTimings) -> Duration)] = &[
+        ("read", |t| t.read),
+        ("parse", |t| t.parse),
+        ("transform", |t| t.transform),
+        ("postprocess", |t| t.postprocess),
+        ("serialize", |t| t.serialize),
+    ];
+
+    for (name, pick) in stages {
+        group.bench_function(*name, |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let timings = make_dict_timed(DMain, &args.options, &pm).unwrap();
+                    total += pick(&timings);
+                }
+                total
+            });
+        });
+    }
 
     std::fs::remove_dir_all(pm.dir_dicts()).unwrap();
+    group.finish();
 }
 
-// cargo run -r -- main el el -r --cache-filter --skip-yomitan --first 50
-fn bench_el_el(c: &mut Criterion) {
-    bench_monolingual(c, EditionLang::El, "main_dict_el_el");
-}
+/// Write a Bencher Metric Format (BMF) style results file when `BENCH_METRICS_PATH` is set.
+///
+/// For each scenario we report `latency` (wall time for a full build, in nanoseconds, with a
+/// min/max band) and a derived `entries_per_second` throughput, computed from the fixture's known
+/// entry count so cross-edition comparisons are meaningful. Normal `cargo bench` runs are
+/// unaffected because the file is only written when the env var is present.
+fn emit_metrics(path: &str) {
+    let fixture_path = Path::new(BENCH_FIXTURES_DIR_100);
+    const SAMPLES: u32 = 10;
 
-fn bench_de_de(c: &mut Criterion) {
-    bench_monolingual(c, EditionLang::De, "main_dict_de_de");
+    let mut report = serde_json::Map::new();
+    for scenario in SCENARIOS {
+        let args = scenario.args(fixture_path);
+        let pm = PathManager::new(DictionaryType::Main, &args);
+
+        let mut samples = Vec::with_capacity(SAMPLES as usize);
+        for _ in 0..SAMPLES {
+            let start = std::time::Instant::now();
+            make_dict_simple(DMain, &args.options, &pm).unwrap();
+            samples.push(start.elapsed().as_nanos() as f64);
+            std::fs::remove_dir_all(pm.dir_dicts()).unwrap();
+        }
+
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let value = samples[samples.len() / 2];
+        let lower = samples[0];
+        let upper = samples[samples.len() - 1];
+        let per_second = scenario.entries as f64 / (value / 1e9);
+
+        report.insert(
+            scenario.name.to_string(),
+            serde_json::json!({
+                "latency": { "value": value, "lower_value": lower, "upper_value": upper },
+                "entries_per_second": { "value": per_second },
+            }),
+        );
+    }
+
+    let json = serde_json::to_string_pretty(&report).unwrap();
+    std::fs::write(path, json).unwrap();
 }
 
-criterion_group!(benches, bench_el_el, bench_de_de);
-criterion_main!(benches);
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_main_dict(&mut criterion);
+    bench_time_to_first_entry(&mut criterion);
+    bench_pipeline_stages(&mut criterion);
+    criterion.final_summary();
+
+    if let Ok(path) = std::env::var("BENCH_METRICS_PATH") {
+        emit_metrics(&path);
+    }
+}
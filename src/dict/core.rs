@@ -8,18 +8,22 @@ use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::PathBuf;
 
 use crate::{Map, Set};
-use crate::cli::Options;
-use crate::dict::writer::write_yomitan;
+use crate::cli::{IrFormat, Options, OutputFormat};
+use crate::dict::writer::{YomitanWriter, write_yomitan};
 use crate::download::DatasetKind;
 use crate::lang::{Edition, EditionSpec, Lang, LangSpec};
 use crate::models::kaikki::WordEntry;
 use crate::models::yomitan::YomitanEntry;
 use crate::path::{PathKind, PathManager};
+use crate::scope::Register;
 use crate::utils::pretty_print_at_path;
 use crate::utils::skip_because_file_exists;
 
 const CONSOLE_PRINT_INTERVAL: i32 = 10000;
 
+/// Default IR size at which a streaming dictionary flushes a batch to its open sink.
+const FLUSH_THRESHOLD: usize = 50_000;
+
 // pub type E = Box<dyn Iterator<Item = YomitanEntry>>;
 pub type E = Vec<YomitanEntry>;
 
@@ -52,6 +56,24 @@ pub trait Intermediate: Default {
         self.len() == 0
     }
 
+    /// Size past which the IR may be flushed early, bounding peak memory on large editions.
+    ///
+    /// `None` (the default) keeps the accumulate-then-convert path. An IR whose items are finished
+    /// the moment they are pushed — i.e. no later line can amend one — may return `Some(n)` so that
+    /// `make_dict` partitions it off with [`drain_ready`](Self::drain_ready) once it reaches `n`.
+    fn flush_threshold(&self) -> Option<usize> {
+        None
+    }
+
+    /// Split off the IR items that are already complete, leaving the rest to keep accumulating.
+    ///
+    /// Only consulted when [`flush_threshold`](Self::flush_threshold) is `Some`; the default drains
+    /// nothing. The returned partition is converted and written immediately, so it must never hold
+    /// an entry a later line could still touch.
+    fn drain_ready(&mut self) -> Self {
+        Self::default()
+    }
+
     /// How to write `Self::I` to disk.
     ///
     /// Only called if `opts.save_temps` is set and `Dictionary::write_ir` returns true.
@@ -59,6 +81,32 @@ pub trait Intermediate: Default {
     fn write(&self, pm: &PathManager) -> Result<()> {
         Ok(())
     }
+
+    /// Write a compact CBOR checkpoint of the IR, reloadable by [`read_binary`](Self::read_binary).
+    ///
+    /// Selected over [`write`](Self::write) when `opts.ir_format` is `Cbor`. Defaults to a no-op so
+    /// a dictionary only pays for binary IR once it opts in.
+    #[allow(unused_variables)]
+    fn write_binary(&self, pm: &PathManager) -> Result<()> {
+        Ok(())
+    }
+
+    /// Reload a CBOR checkpoint written by [`write_binary`](Self::write_binary).
+    ///
+    /// Drives the `--reuse-ir` fast path; the default errors so dictionaries that don't implement a
+    /// reload gracefully fall back to a full rebuild.
+    #[allow(unused_variables)]
+    fn read_binary(pm: &PathManager) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        anyhow::bail!("binary IR reload is not supported for this dictionary")
+    }
+}
+
+/// Path of the CBOR IR checkpoint for the resolved source/target in `pm`.
+pub(crate) fn ir_binary_path(pm: &PathManager) -> PathBuf {
+    pm.dir_tidy().join("tidy.cbor")
 }
 
 impl<T> Intermediate for Vec<T>
@@ -69,6 +117,25 @@ where
         Self::len(self)
     }
 
+    fn flush_threshold(&self) -> Option<usize> {
+        // A `Vec` IR is append-only: each pushed entry is final, so the whole buffer is drainable.
+        Some(FLUSH_THRESHOLD)
+    }
+
+    fn drain_ready(&mut self) -> Self {
+        std::mem::take(self)
+    }
+
+    fn write_binary(&self, pm: &PathManager) -> Result<()> {
+        let writer_path = ir_binary_path(pm);
+        let writer = BufWriter::new(File::create(&writer_path)?);
+        ciborium::into_writer(self, writer).map_err(|e| anyhow::anyhow!(e))?;
+        if !pm.opts.quiet {
+            pretty_print_at_path("Wrote binary IR", &writer_path);
+        }
+        Ok(())
+    }
+
     fn write(&self, pm: &PathManager) -> Result<()> {
         let writer_path = pm.dir_tidy().join("tidy.jsonl");
         let writer_file = File::create(&writer_path)?;
@@ -100,6 +167,25 @@ pub trait Dictionary {
         false
     }
 
+    /// Whether this dictionary may flush IR batches while reading instead of accumulating whole
+    /// editions.
+    ///
+    /// Safe only when [`langs_to_key`](IterLang::langs_to_key) keeps each edition in its own bucket
+    /// (`EditionSpec::One`); merged dictionaries need every edition before `postprocess`, so they
+    /// leave this `false`. Streaming additionally requires the IR to opt in via
+    /// [`Intermediate::flush_threshold`].
+    fn streams(&self) -> bool {
+        false
+    }
+
+    /// Entry-level register, used by `--include-scopes`/`--exclude-scopes` to size a build.
+    ///
+    /// Defaults to the shared heuristic; a dictionary whose data suggests a better signal (e.g. IPA
+    /// presence) can refine it.
+    fn scope_of(&self, entry: &WordEntry, source: Lang) -> Register {
+        crate::scope::classify(entry, source)
+    }
+
     // NOTE: Maybe we can get rid of this (blocked by mutable behaviour of the main dictionary).
     //
     /// How to preprocess a `WordEntry`. Everything that mutates `entry` should go here.
@@ -131,15 +217,29 @@ pub trait Dictionary {
     ///
     /// This can be implemented to merge entries from different edition, to postprocess tags etc.
     #[allow(unused_variables)]
-    fn postprocess(&self, irs: &mut Self::I) {}
+    fn postprocess(&self, irs: &mut Self::I, opts: &Options) {}
 
     /// How to convert `Self::I` into one or more yomitan entries.
     fn to_yomitan(&self, langs: Langs, irs: Self::I) -> Vec<LabelledYomitanEntry>;
 }
 
 fn rejected(entry: &WordEntry, opts: &Options) -> bool {
-    opts.reject.iter().any(|(k, v)| k.field_value(entry) == v)
-        || !opts.filter.iter().all(|(k, v)| k.field_value(entry) == v)
+    opts.reject
+        .iter()
+        .any(|(k, v)| k.matches(entry, v, opts.normalization))
+        || !opts
+            .filter
+            .iter()
+            .all(|(k, v)| k.matches(entry, v, opts.normalization))
+}
+
+/// Whether an entry's register excludes it from the build.
+///
+/// `--exclude-scopes` drops the listed registers; a non-empty `--include-scopes` flips the gate to
+/// an allow-list. With neither set every register is kept.
+fn scope_rejected(register: Register, opts: &Options) -> bool {
+    opts.exclude_scopes.contains(&register)
+        || (!opts.include_scopes.is_empty() && !opts.include_scopes.contains(&register))
 }
 
 #[inline]
@@ -297,6 +397,104 @@ fn probe_top_level_lang_code(line: &[u8]) -> Option<&str> {
     }
 }
 
+/// Single-pass probe for the top-level string fields named in `keys`.
+///
+/// Generalizes [`probe_top_level_lang_code`] to any set of scalar keys: it walks the object once,
+/// reusing the same skip helpers to step over nested compounds and string bodies, and records the
+/// value of each requested key that is a plain, unescaped string. A key whose value is not a string
+/// (or carries a backslash escape) is left out, so a map shorter than `keys` tells the caller the
+/// line can't be judged from the probe alone and a full parse is needed.
+fn probe_top_level_fields<'a>(line: &'a [u8], keys: &Set<&str>) -> Map<&'a str, &'a str> {
+    let mut out = Map::default();
+    let mut i = skip_json_whitespace(line, 0);
+    if line.get(i).copied() != Some(b'{') {
+        return out;
+    }
+    i += 1;
+
+    loop {
+        i = skip_json_whitespace(line, i);
+        if line.get(i).copied() != Some(b'"') {
+            return out;
+        }
+
+        let key_start = i + 1;
+        i = match skip_json_string(line, i) {
+            Some(i) => i,
+            None => return out,
+        };
+        let key_end = i - 1;
+
+        i = skip_json_whitespace(line, i);
+        if line.get(i).copied() != Some(b':') {
+            return out;
+        }
+        i += 1;
+        i = skip_json_whitespace(line, i);
+
+        let wanted = std::str::from_utf8(&line[key_start..key_end])
+            .ok()
+            .filter(|key| keys.contains(key));
+        if let Some(key) = wanted {
+            if line.get(i).copied() == Some(b'"') {
+                let value_start = i + 1;
+                let next = match skip_json_string(line, i) {
+                    Some(next) => next,
+                    None => return out,
+                };
+                let value = &line[value_start..next - 1];
+                if !value.contains(&b'\\') {
+                    if let Ok(value) = std::str::from_utf8(value) {
+                        out.insert(key, value);
+                    }
+                }
+                i = next;
+            } else {
+                i = match skip_json_value(line, i) {
+                    Some(i) => i,
+                    None => return out,
+                };
+            }
+        } else {
+            i = match skip_json_value(line, i) {
+                Some(i) => i,
+                None => return out,
+            };
+        }
+
+        if out.len() == keys.len() {
+            return out;
+        }
+
+        i = skip_json_whitespace(line, i);
+        match line.get(i).copied() {
+            Some(b',') => i += 1,
+            _ => return out,
+        }
+    }
+}
+
+/// Evaluate `opts.filter`/`opts.reject` against values lifted straight from the JSON line.
+///
+/// Mirrors [`rejected`] but reads from a [`probe_top_level_fields`] map, so a line can be dropped
+/// before it is ever handed to serde. Callers must first check that the probe yielded every
+/// requested key; this only decides the predicate.
+fn rejected_from_probe(probed: &Map<&str, &str>, opts: &Options) -> bool {
+    let hit = |k: &crate::cli::FilterKey, v: &crate::cli::FilterValue| {
+        probed.get(k.json_key()).is_some_and(|s| match (k, v) {
+            // Same normalization of both sides as `FilterKey::matches` applies to `Word`.
+            (crate::cli::FilterKey::Word, crate::cli::FilterValue::Exact(exact)) => {
+                opts.normalization.apply(s) == opts.normalization.apply(exact)
+            }
+            (crate::cli::FilterKey::Word, crate::cli::FilterValue::Regex(re)) => {
+                re.is_match(&opts.normalization.apply(s))
+            }
+            _ => v.matches_str(s),
+        })
+    };
+    opts.reject.iter().any(|(k, v)| hit(k, v)) || !opts.filter.iter().all(|(k, v)| hit(k, v))
+}
+
 #[derive(Deserialize)]
 #[serde(default)]
 struct LangCodeProbe<'a> {
@@ -580,6 +778,16 @@ fn iter_datasets<'a, D: DatasetStrategy>(
     })
 }
 
+/// Open a streaming writer for `langs`, mirroring the per-key `PathManager` setup `make_dict` does
+/// before the one-shot `write_yomitan`.
+fn open_stream_writer(langs: Langs, opts: &Options, pm: &PathManager) -> Result<YomitanWriter> {
+    let mut pm2 = pm.clone();
+    pm2.set_source(langs.source.into());
+    pm2.set_target(langs.target.into());
+    pm2.setup_dirs()?;
+    YomitanWriter::open(langs.source, langs.target, opts, &pm2)
+}
+
 pub fn make_dict<D: Dictionary + IterLang + DatasetStrategy>(
     dict: D,
     raw_args: D::A,
@@ -595,7 +803,43 @@ pub fn make_dict<D: Dictionary + IterLang + DatasetStrategy>(
     // (source, target) -> D::I
     let mut irs_map: Map<LangsKey, D::I> = Map::default();
 
+    // Streaming only applies to the default zip output; `--save-temps` and sqlite keep the
+    // one-shot path since they don't share `YomitanWriter`'s incremental sink.
+    let stream_enabled = dict.streams()
+        && !opts.skip_yomitan
+        && !opts.save_temps
+        && opts.output_format == OutputFormat::Zip;
+    // Per key, an open zip that receives flushed batches while the edition is still being read.
+    let mut writers: Map<LangsKey, YomitanWriter> = Map::default();
+
+    // `--reuse-ir`: when a binary checkpoint is on disk, load it as the single resolved IR and skip
+    // the whole read/parse/process loop. Reload failures fall back to a full rebuild, so a missing
+    // or stale checkpoint is never fatal.
+    let mut reused = false;
+    if opts.reuse_ir && ir_binary_path(pm).exists() {
+        match D::I::read_binary(pm) {
+            std::result::Result::Ok(irs) => {
+                // Store the reloaded IR under the first key the resolved langs map to.
+                let (edition_pm, _, _) = pm.langs();
+                if let Some(edition) = edition_pm.variants().into_iter().next()
+                    && let Some(langs) =
+                        dict.iter_langs(edition, source_pm, target_pm).into_iter().next()
+                {
+                    irs_map.insert(dict.langs_to_key(langs), irs);
+                    if !opts.quiet {
+                        pretty_print_at_path("Reusing binary IR", &ir_binary_path(pm));
+                    }
+                    reused = true;
+                }
+            }
+            Err(err) => tracing::debug!("binary IR reload failed, rebuilding: {err}"),
+        }
+    }
+
     for pair in iter_datasets(&dict, pm) {
+        if reused {
+            break;
+        }
         let (edition, path_jsonl) = pair?;
         let langs_for_edition = dict.iter_langs(edition, source_pm, target_pm);
         let lang_code_prefilter = if dict.supports_lang_code_prefilter()
@@ -613,6 +857,22 @@ pub fn make_dict<D: Dictionary + IterLang + DatasetStrategy>(
             None
         };
 
+        // Top-level keys touched by `--filter`/`--reject`; when a line exposes all of them as
+        // plain strings we can judge it straight from the raw bytes and skip deserializing the
+        // rejects entirely.
+        let field_prefilter: Option<Set<&str>> =
+            if !opts.filter.is_empty() || !opts.reject.is_empty() {
+                Some(
+                    opts.reject
+                        .iter()
+                        .chain(opts.filter.iter())
+                        .map(|(k, _)| k.json_key())
+                        .collect(),
+                )
+            } else {
+                None
+            };
+
         let reader_file = File::open(&path_jsonl)?;
         let mut reader = BufReader::with_capacity(capacity, reader_file);
 
@@ -646,10 +906,26 @@ pub fn make_dict<D: Dictionary + IterLang + DatasetStrategy>(
                 }
             }
 
+            // Drop rejects before deserializing when the probe can see every filtered key; a short
+            // probe (nested/escaped/absent value) leaves the decision to the serde pass below.
+            let mut prefiltered = false;
+            if let Some(field_prefilter) = &field_prefilter {
+                let probed = probe_top_level_fields(&line, field_prefilter);
+                if probed.len() == field_prefilter.len() {
+                    if rejected_from_probe(&probed, opts) {
+                        continue;
+                    }
+                    prefiltered = true;
+                }
+            }
+
             let mut entry: WordEntry =
                 serde_json::from_slice(&line).with_context(|| "Error decoding JSON @ make_dict")?;
 
-            if (!opts.filter.is_empty() || !opts.reject.is_empty()) && rejected(&entry, opts) {
+            if !prefiltered
+                && (!opts.filter.is_empty() || !opts.reject.is_empty())
+                && rejected(&entry, opts)
+            {
                 continue;
             }
 
@@ -658,12 +934,35 @@ pub fn make_dict<D: Dictionary + IterLang + DatasetStrategy>(
                 break;
             }
 
+            if let Some(form_filter) = &opts.form_filter {
+                entry
+                    .forms
+                    .retain(|form| !crate::tags::matches(form_filter, &form.tags));
+            }
+
             for &langs in &langs_for_edition {
+                if scope_rejected(dict.scope_of(&entry, langs.source), opts) {
+                    continue;
+                }
                 if dict.keep_if(langs.source, &entry) {
                     let key = dict.langs_to_key(langs);
                     let irs = irs_map.entry(key).or_default();
                     dict.preprocess(langs, &mut entry, opts, irs);
                     dict.process(langs, &entry, irs);
+
+                    // Flush the finished part of the IR early once it grows past the threshold,
+                    // appending it to this key's open writer so peak memory stays bounded.
+                    if stream_enabled
+                        && let Some(threshold) = irs.flush_threshold()
+                        && irs.len() >= threshold
+                    {
+                        let batch = dict.to_yomitan(langs, irs.drain_ready());
+                        if !writers.contains_key(&dict.langs_to_key(langs)) {
+                            let writer = open_stream_writer(langs, opts, pm)?;
+                            writers.insert(dict.langs_to_key(langs), writer);
+                        }
+                        writers[&dict.langs_to_key(langs)].append(batch)?;
+                    }
                 }
             }
         }
@@ -688,14 +987,31 @@ pub fn make_dict<D: Dictionary + IterLang + DatasetStrategy>(
             dict.found_ir_message(&key, &irs);
         }
 
+        // A streaming key already emitted most of its entries; append whatever is left and close
+        // its writer instead of taking the one-shot path.
+        if let Some(mut writer) = writers.swap_remove(&key) {
+            if !irs.is_empty() {
+                let langs = match key.edition {
+                    EditionSpec::All => Langs::new(Edition::Zh, key.source, key.target),
+                    EditionSpec::One(edition) => Langs::new(edition, key.source, key.target),
+                };
+                writer.append(dict.to_yomitan(langs, irs))?;
+            }
+            writer.finish()?;
+            continue;
+        }
+
         if irs.is_empty() {
             continue;
         }
 
-        dict.postprocess(&mut irs);
+        dict.postprocess(&mut irs, opts);
 
         if opts.save_temps && dict.write_ir() {
-            irs.write(pm)?;
+            match opts.ir_format {
+                IrFormat::Jsonl => irs.write(pm)?,
+                IrFormat::Cbor => irs.write_binary(pm)?,
+            }
         }
 
         if !opts.skip_yomitan {
@@ -727,7 +1043,8 @@ pub fn make_dict<D: Dictionary + IterLang + DatasetStrategy>(
 
 #[cfg(test)]
 mod tests {
-    use super::probe_top_level_lang_code;
+    use super::{probe_top_level_fields, probe_top_level_lang_code};
+    use crate::Set;
 
     #[test]
     fn probe_lang_code_returns_top_level_value() {
@@ -758,4 +1075,25 @@ mod tests {
         let line = br#"{"word":"x","translations":[{"lang_code":"en"}]}"#;
         assert_eq!(probe_top_level_lang_code(line), None);
     }
+
+    #[test]
+    fn probe_fields_collects_requested_scalars() {
+        let line = br#"{"word":"λόγος","lang_code":"el","pos":"noun","senses":[{"x":1}]}"#;
+        let keys: Set<&str> = ["lang_code", "pos"].into_iter().collect();
+        let probed = probe_top_level_fields(line, &keys);
+        assert_eq!(probed.get("lang_code"), Some(&"el"));
+        assert_eq!(probed.get("pos"), Some(&"noun"));
+        assert_eq!(probed.len(), 2);
+    }
+
+    #[test]
+    fn probe_fields_skips_escaped_value() {
+        let line = br#"{"word":"a\"b","lang_code":"el"}"#;
+        let keys: Set<&str> = ["word", "lang_code"].into_iter().collect();
+        let probed = probe_top_level_fields(line, &keys);
+        // The escaped `word` is left out, so the map is short and the caller falls back to serde.
+        assert!(!probed.contains_key("word"));
+        assert_eq!(probed.get("lang_code"), Some(&"el"));
+        assert!(probed.len() < keys.len());
+    }
 }
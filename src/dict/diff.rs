@@ -0,0 +1,170 @@
+//! Structural, JSON-aware diff for the snapshot test harness.
+//!
+//! `check_git_diff` compares generated Yomitan banks with a line-based unified diff, which is noisy
+//! whenever unrelated entries merely shift line position. This module instead parses a bank (a
+//! `term_bank_*.json`-shaped top-level JSON array) from both the committed snapshot and the fresh
+//! output, keys each row by its headword (and reading, to disambiguate homographs), and reports a
+//! semantic changeset: entries added, entries removed, and for entries present in both, the
+//! minimal set of changed field paths. Matching is by key, not position, so pure reordering of
+//! independent entries is not reported as a change. Selected via the `KTY_SNAPSHOT_DIFF=structural`
+//! env var as an alternative to the git-diff step in `shapshot_main`.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+/// One row's identity within a term bank: `[term, reading, ...]` (Yomitan's own row convention),
+/// keyed on the first two array slots so homographs with distinct readings don't collide.
+fn row_key(row: &Value) -> String {
+    match row.as_array() {
+        Some(fields) => {
+            let term = fields.first().and_then(Value::as_str).unwrap_or("");
+            let reading = fields.get(1).and_then(Value::as_str).unwrap_or("");
+            format!("{term}\u{0}{reading}")
+        }
+        None => row.to_string(),
+    }
+}
+
+/// A semantic changeset between two term banks.
+#[derive(Debug, Default)]
+pub struct BankDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Key -> minimal set of changed field paths (e.g. `[5][0]` for the first gloss).
+    pub changed: BTreeMap<String, Vec<String>>,
+}
+
+impl BankDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// One-line "N entries changed, M fields modified"-style summary.
+    pub fn summary(&self) -> String {
+        let changed_fields: usize = self.changed.values().map(Vec::len).sum();
+        format!(
+            "{} entries added, {} entries removed, {} entries changed ({changed_fields} fields modified)",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len(),
+        )
+    }
+}
+
+/// Diff two term banks (each the parsed top-level array of a `term_bank_*.json` file), matching
+/// rows by key rather than position.
+pub fn diff_bank(old: &[Value], new: &[Value]) -> BankDiff {
+    let old_by_key: BTreeMap<String, &Value> = old.iter().map(|row| (row_key(row), row)).collect();
+    let new_by_key: BTreeMap<String, &Value> = new.iter().map(|row| (row_key(row), row)).collect();
+
+    let mut diff = BankDiff::default();
+    for key in old_by_key.keys() {
+        if !new_by_key.contains_key(key) {
+            diff.removed.push(key.clone());
+        }
+    }
+    for (key, new_row) in &new_by_key {
+        match old_by_key.get(key) {
+            None => diff.added.push(key.clone()),
+            Some(old_row) => {
+                let mut paths = Vec::new();
+                diff_value(old_row, new_row, &mut String::new(), &mut paths);
+                if !paths.is_empty() {
+                    diff.changed.insert(key.clone(), paths);
+                }
+            }
+        }
+    }
+    diff
+}
+
+/// Recursively collect the minimal set of changed field paths between `old` and `new`, appending
+/// bracketed/dotted paths (`[5][0]`, `.glossary`) onto `changed`.
+fn diff_value(old: &Value, new: &Value, path: &mut String, changed: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            for idx in 0..old_items.len().max(new_items.len()) {
+                let prefix_len = path.len();
+                path.push_str(&format!("[{idx}]"));
+                match (old_items.get(idx), new_items.get(idx)) {
+                    (Some(o), Some(n)) => diff_value(o, n, path, changed),
+                    _ => changed.push(path.clone()),
+                }
+                path.truncate(prefix_len);
+            }
+        }
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            for key in keys {
+                let prefix_len = path.len();
+                path.push('.');
+                path.push_str(key);
+                match (old_map.get(key), new_map.get(key)) {
+                    (Some(o), Some(n)) => diff_value(o, n, path, changed),
+                    _ => changed.push(path.clone()),
+                }
+                path.truncate(prefix_len);
+            }
+        }
+        _ if old != new => changed.push(path.clone()),
+        _ => {}
+    }
+}
+
+/// Parse a `term_bank_*.json` file's top-level array of rows. Returns an empty vec for anything
+/// that doesn't parse as a JSON array (e.g. a bank added/removed wholesale between the snapshot and
+/// the fresh regeneration).
+pub fn read_bank(contents: &str) -> Vec<Value> {
+    serde_json::from_str(contents).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reordering_independent_entries_is_not_a_change() {
+        let old = vec![
+            json!(["a", "", [], "", 0, ["gloss a"]]),
+            json!(["b", "", [], "", 0, ["gloss b"]]),
+        ];
+        let new = vec![
+            json!(["b", "", [], "", 0, ["gloss b"]]),
+            json!(["a", "", [], "", 0, ["gloss a"]]),
+        ];
+        assert!(diff_bank(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn detects_added_and_removed_entries() {
+        let old = vec![json!(["a", "", [], "", 0, ["gloss a"]])];
+        let new = vec![json!(["b", "", [], "", 0, ["gloss b"]])];
+        let diff = diff_bank(&old, &new);
+        assert_eq!(diff.added, vec!["b\u{0}"]);
+        assert_eq!(diff.removed, vec!["a\u{0}"]);
+    }
+
+    #[test]
+    fn reports_minimal_changed_path_for_a_changed_gloss() {
+        let old = vec![json!(["a", "", [], "", 0, ["old gloss"]])];
+        let new = vec![json!(["a", "", [], "", 0, ["new gloss"]])];
+        let diff = diff_bank(&old, &new);
+        assert_eq!(diff.changed["a\u{0}"], vec!["[5][0]"]);
+    }
+
+    #[test]
+    fn homographs_with_distinct_readings_do_not_collide() {
+        let old = vec![json!(["lead", "li:d", [], "", 0, []])];
+        let new = vec![
+            json!(["lead", "li:d", [], "", 0, []]),
+            json!(["lead", "lɛd", [], "", 0, []]),
+        ];
+        let diff = diff_bank(&old, &new);
+        assert_eq!(diff.added, vec!["lead\u{0}lɛd"]);
+        assert!(diff.changed.is_empty());
+    }
+}
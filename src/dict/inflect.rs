@@ -0,0 +1,365 @@
+//! Pluggable algorithmic inflection-generation engine.
+//!
+//! Some editions' wiktextract dumps omit most non-lemma forms (no forms table, or one too sparse
+//! to be useful). This module synthesizes deinflections instead, by rewriting a lemma's stem
+//! through a declension/conjugation class: an ordered list of `(slot_tags, transform)` rules, each
+//! `transform` either a sequence of regex substitutions or an epenthesis-aware suffix attachment,
+//! applied to a stem extracted from the lemma by the class's own stem pattern. See [`synthesize`]
+//! for the entry point and [`CLASSES`] for the shipped class tables. Gated behind
+//! `ArgsOptions::synthesize_inflections` so it stays opt-in per language.
+
+use regex::Regex;
+
+use crate::Set;
+use crate::models::kaikki::Tag;
+
+/// A word split at its last vowel into what precedes it (`rest`), the vowel itself (`nucleus`) and
+/// whatever consonants trail it (`coda`). Exposed for paradigm modules that need to reason about a
+/// stem's shape directly rather than through a regex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stem {
+    pub rest: String,
+    pub nucleus: char,
+    pub coda: String,
+}
+
+const VOWELS: [char; 10] = ['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U'];
+
+impl Stem {
+    /// Split `word` at its last vowel. `None` if `word` has no vowel at all.
+    pub fn split(word: &str) -> Option<Self> {
+        let nucleus_idx = word.rfind(VOWELS)?;
+        let nucleus = word[nucleus_idx..]
+            .chars()
+            .next()
+            .expect("match is non-empty");
+        Some(Self {
+            rest: word[..nucleus_idx].to_string(),
+            nucleus,
+            coda: word[nucleus_idx + nucleus.len_utf8()..].to_string(),
+        })
+    }
+
+    pub fn surface(&self) -> String {
+        format!("{}{}{}", self.rest, self.nucleus, self.coda)
+    }
+}
+
+/// Collapse a doubled final consonant (`tt$` -> `t`) or a vowel-glide sequence left over from
+/// suffixation (`uw$` -> `w`).
+pub fn degeminate(form: &str) -> String {
+    if let Some(trimmed) = form.strip_suffix("uw") {
+        return format!("{trimmed}w");
+    }
+    let mut chars = form.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(last), Some(before)) if last == before && !VOWELS.contains(&last) => {
+            form[..form.len() - last.len_utf8()].to_string()
+        }
+        _ => form.to_string(),
+    }
+}
+
+/// Insert the epenthetic segment `table` maps the stem's final letter to, then attach `suffix`.
+/// Letters absent from `table` attach the suffix directly.
+pub fn epenthesize(stem: &str, suffix: &str, table: &[(char, &str)]) -> String {
+    let insert = stem
+        .chars()
+        .next_back()
+        .and_then(|last| table.iter().find(|(trigger, _)| *trigger == last))
+        .map_or("", |(_, insert)| insert);
+    format!("{stem}{insert}{suffix}")
+}
+
+/// Attach `suffix` to `stem`, consulting `sandhi` first for an override keyed on the stem's
+/// trailing one or two characters (checked in order, so list longer endings before shorter ones);
+/// falling back to [`epenthesize`] when nothing matches.
+pub fn attach_suffix(
+    stem: &str,
+    suffix: &str,
+    sandhi: &[(&str, &str)],
+    epenthesis: &[(char, &str)],
+) -> String {
+    for (ending, replacement) in sandhi {
+        if let Some(trimmed) = stem.strip_suffix(ending) {
+            return format!("{trimmed}{replacement}{suffix}");
+        }
+    }
+    epenthesize(stem, suffix, epenthesis)
+}
+
+/// One regex substitution step within a [`Transform::Rewrites`].
+pub struct Rewrite {
+    pattern: &'static str,
+    replacement: &'static str,
+}
+
+/// How a rule turns a class's extracted stem into one inflected surface form.
+pub enum Transform {
+    /// An ordered sequence of regex substitutions, applied left to right.
+    Rewrites(&'static [Rewrite]),
+    /// [`attach_suffix`] with the given sandhi and epenthesis tables.
+    Suffix {
+        sandhi: &'static [(&'static str, &'static str)],
+        epenthesis: &'static [(char, &'static str)],
+        suffix: &'static str,
+    },
+}
+
+impl Transform {
+    fn apply(&self, stem: &str) -> String {
+        match self {
+            Self::Rewrites(rewrites) => {
+                let mut surface = stem.to_string();
+                for rewrite in *rewrites {
+                    let re = Regex::new(rewrite.pattern).expect("static inflection rewrite regex");
+                    surface = re.replace(&surface, rewrite.replacement).into_owned();
+                }
+                surface
+            }
+            Self::Suffix {
+                sandhi,
+                epenthesis,
+                suffix,
+            } => attach_suffix(stem, suffix, sandhi, epenthesis),
+        }
+    }
+}
+
+/// One declension/conjugation paradigm: a regex carving the lemma into a stem (its first capture
+/// group), plus the ordered `(slot_tags, transform)` rules rewriting that stem into each surface
+/// form.
+pub struct InflectionClass {
+    pub id: &'static str,
+    stem_pattern: &'static str,
+    rules: &'static [(&'static [&'static str], Transform)],
+}
+
+impl InflectionClass {
+    fn stem(&self, lemma: &str, stem_override: Option<&str>) -> Option<String> {
+        if let Some(stem) = stem_override {
+            return Some(stem.to_string());
+        }
+        let re = Regex::new(self.stem_pattern).expect("static class stem regex");
+        Some(re.captures(lemma)?.get(1)?.as_str().to_string())
+    }
+}
+
+/// Latin 1st-declension noun (`-a`, stem the rest of the lemma).
+static LATIN_FIRST_DECLENSION: InflectionClass = InflectionClass {
+    id: "la-1st-declension",
+    stem_pattern: r"^(.*)a$",
+    rules: &[
+        (
+            &["genitive", "singular"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "ae",
+            }]),
+        ),
+        (
+            &["dative", "singular"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "ae",
+            }]),
+        ),
+        (
+            &["accusative", "singular"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "am",
+            }]),
+        ),
+        (
+            &["ablative", "singular"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "\u{101}",
+            }]),
+        ),
+        (
+            &["nominative", "plural"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "ae",
+            }]),
+        ),
+        (
+            &["genitive", "plural"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "\u{101}rum",
+            }]),
+        ),
+        (
+            &["dative", "plural"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "\u{12b}s",
+            }]),
+        ),
+        (
+            &["accusative", "plural"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "\u{101}s",
+            }]),
+        ),
+        (
+            &["ablative", "plural"],
+            Transform::Rewrites(&[Rewrite {
+                pattern: "$",
+                replacement: "\u{12b}s",
+            }]),
+        ),
+    ],
+};
+
+/// Epenthesis tables for [`BASQUE_STYLE_NOUN`]: a vowel-final stem takes a linking `r` before a
+/// vowel-initial suffix, a consonant-final stem takes a linking `e` before a consonant-initial one.
+const BASQUE_VOWEL_LINK: [(char, &str); 5] =
+    [('a', "r"), ('e', "r"), ('i', "r"), ('o', "r"), ('u', "r")];
+const BASQUE_CONSONANT_LINK: [(char, &str); 6] = [
+    ('r', "e"),
+    ('n', "e"),
+    ('l', "e"),
+    ('t', "e"),
+    ('k', "e"),
+    ('s', "e"),
+];
+
+/// Simplified Basque-style noun paradigm demonstrating epenthesis: the absolutive attaches
+/// directly, but the vowel-initial genitive and consonant-initial ergative each need a linking
+/// segment on the opposite stem ending. Illustrative, not a reference grammar.
+static BASQUE_STYLE_NOUN: InflectionClass = InflectionClass {
+    id: "eu-noun-epenthesis",
+    stem_pattern: r"^(.*)$",
+    rules: &[
+        (
+            &["absolutive", "singular"],
+            Transform::Suffix {
+                sandhi: &[],
+                epenthesis: &[],
+                suffix: "a",
+            },
+        ),
+        (
+            &["ergative", "singular"],
+            Transform::Suffix {
+                sandhi: &[],
+                epenthesis: &BASQUE_CONSONANT_LINK,
+                suffix: "k",
+            },
+        ),
+        (
+            &["genitive", "singular"],
+            Transform::Suffix {
+                sandhi: &[],
+                epenthesis: &BASQUE_VOWEL_LINK,
+                suffix: "en",
+            },
+        ),
+        (
+            &["dative", "singular"],
+            Transform::Suffix {
+                sandhi: &[],
+                epenthesis: &BASQUE_VOWEL_LINK,
+                suffix: "i",
+            },
+        ),
+    ],
+};
+
+static CLASSES: [&InflectionClass; 2] = [&LATIN_FIRST_DECLENSION, &BASQUE_STYLE_NOUN];
+
+/// Generate deinflections for `lemma` under `class_id`, optionally overriding the stem the class
+/// would otherwise extract itself. Results are deduplicated and filtered against the lemma, ready
+/// to hand to `Tidy::insert_form` with `FormSource::Inflection`.
+pub fn synthesize(
+    lemma: &str,
+    class_id: &str,
+    stem_override: Option<&str>,
+) -> Vec<(String, Vec<Tag>)> {
+    let Some(class) = CLASSES.iter().find(|class| class.id == class_id) else {
+        return Vec::new();
+    };
+    let Some(stem) = class.stem(lemma, stem_override) else {
+        return Vec::new();
+    };
+
+    let mut seen: Set<String> = Set::default();
+    let mut out = Vec::new();
+    for (slot_tags, transform) in class.rules {
+        let surface = degeminate(&transform.apply(&stem));
+        if surface == lemma || !seen.insert(surface.clone()) {
+            continue;
+        }
+        out.push((
+            surface,
+            slot_tags.iter().map(|tag| (*tag).to_string()).collect(),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_stem_at_last_vowel() {
+        let stem = Stem::split("fama").unwrap();
+        assert_eq!(stem.rest, "fam");
+        assert_eq!(stem.nucleus, 'a');
+        assert_eq!(stem.coda, "");
+        assert_eq!(stem.surface(), "fama");
+        assert!(Stem::split("psst").is_none());
+    }
+
+    #[test]
+    fn degeminate_collapses_doubled_consonant_and_glide() {
+        assert_eq!(degeminate("katt"), "kat");
+        assert_eq!(degeminate("kaduw"), "kadw");
+        assert_eq!(degeminate("kadu"), "kadu");
+    }
+
+    #[test]
+    fn attach_suffix_prefers_sandhi_then_epenthesis() {
+        let sandhi = &[("y", "i")];
+        let epenthesis = &[('s', "e")];
+        assert_eq!(attach_suffix("fly", "es", sandhi, epenthesis), "flies");
+        assert_eq!(attach_suffix("bus", "s", &[], epenthesis), "buses");
+        assert_eq!(attach_suffix("cat", "s", &[], epenthesis), "cats");
+    }
+
+    #[test]
+    fn synthesizes_latin_first_declension() {
+        let forms = synthesize("fama", "la-1st-declension", None);
+        assert!(forms.contains(&(
+            "fam\u{101}".to_string(),
+            vec!["ablative".to_string(), "singular".to_string()]
+        )));
+        assert!(forms.iter().all(|(surface, _)| surface != "fama"));
+    }
+
+    #[test]
+    fn synthesizes_basque_style_epenthesis() {
+        let forms = synthesize("etxe", "eu-noun-epenthesis", None);
+        assert!(forms.contains(&(
+            "etxeren".to_string(),
+            vec!["genitive".to_string(), "singular".to_string()]
+        )));
+
+        let forms = synthesize("lur", "eu-noun-epenthesis", None);
+        assert!(forms.contains(&(
+            "lurek".to_string(),
+            vec!["ergative".to_string(), "singular".to_string()]
+        )));
+    }
+
+    #[test]
+    fn unknown_class_id_returns_nothing() {
+        assert!(synthesize("fama", "nonexistent", None).is_empty());
+    }
+}
@@ -1,20 +1,125 @@
+//! Localization catalog for dictionary section labels.
+//!
+//! Structured-content builders label each section (examples, etymology, synonyms, …) in the
+//! target language. Rather than a match arm per language, the strings live in a small keyed
+//! [`CATALOG`]: adding a UI language (or a new section) is a data edit. Lookups fall back to the
+//! English entry, and from there to the key's own identifier, so a missing translation degrades
+//! gracefully instead of panicking.
+
 use crate::lang::Lang;
 
-// This should be done differently, and support every section of the dictionary (i.e. Etymology)
-
-pub fn localize_examples_string(target: Lang, n: usize) -> String {
-    let (singular, plural) = match target {
-        Lang::Fr => ("exemple", "exemples"),
-        Lang::De => ("Beispiel", "Beispiele"),
-        Lang::Es => ("ejemplo", "ejemplos"),
-        Lang::Ru => ("пример", "примеры"),
-        Lang::Zh | Lang::Ja => return format!("{n} 例"), // special case
-        _ => ("example", "examples"),
-    };
-
-    if n == 1 {
-        format!("1 {singular}")
-    } else {
-        format!("{n} {plural}")
+/// A localizable dictionary section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Examples,
+    Etymology,
+    Synonyms,
+    Antonyms,
+    RelatedTerms,
+    DerivedTerms,
+}
+
+impl Section {
+    /// The catalog key, also used as the last-resort English-ish fallback label.
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Examples => "examples",
+            Self::Etymology => "etymology",
+            Self::Synonyms => "synonyms",
+            Self::Antonyms => "antonyms",
+            Self::RelatedTerms => "related terms",
+            Self::DerivedTerms => "derived terms",
+        }
+    }
+}
+
+/// Singular/plural label forms for one (language, section) pair.
+///
+/// The two forms coincide for languages that do not inflect the label (e.g. the CJK counter `例`).
+#[derive(Clone, Copy)]
+struct Label {
+    singular: &'static str,
+    plural: &'static str,
+}
+
+/// The string catalog, keyed by `(Lang, Section)`.
+///
+/// English is the implicit fallback: every section must have an `En` row, and other languages only
+/// need rows for the sections they translate.
+#[rustfmt::skip]
+const CATALOG: &[(Lang, Section, Label)] = &[
+    (Lang::En, Section::Examples,     Label { singular: "example",      plural: "examples" }),
+    (Lang::Fr, Section::Examples,     Label { singular: "exemple",      plural: "exemples" }),
+    (Lang::De, Section::Examples,     Label { singular: "Beispiel",     plural: "Beispiele" }),
+    (Lang::Es, Section::Examples,     Label { singular: "ejemplo",      plural: "ejemplos" }),
+    (Lang::Ru, Section::Examples,     Label { singular: "пример",       plural: "примеры" }),
+    (Lang::Zh, Section::Examples,     Label { singular: "例",           plural: "例" }),
+    (Lang::Ja, Section::Examples,     Label { singular: "例",           plural: "例" }),
+
+    (Lang::En, Section::Etymology,    Label { singular: "etymology",    plural: "etymologies" }),
+    (Lang::Fr, Section::Etymology,    Label { singular: "étymologie",   plural: "étymologies" }),
+    (Lang::De, Section::Etymology,    Label { singular: "Etymologie",   plural: "Etymologien" }),
+    (Lang::Es, Section::Etymology,    Label { singular: "etimología",   plural: "etimologías" }),
+
+    (Lang::En, Section::Synonyms,     Label { singular: "synonym",      plural: "synonyms" }),
+    (Lang::Fr, Section::Synonyms,     Label { singular: "synonyme",     plural: "synonymes" }),
+    (Lang::De, Section::Synonyms,     Label { singular: "Synonym",      plural: "Synonyme" }),
+    (Lang::Es, Section::Synonyms,     Label { singular: "sinónimo",     plural: "sinónimos" }),
+
+    (Lang::En, Section::Antonyms,     Label { singular: "antonym",      plural: "antonyms" }),
+    (Lang::Fr, Section::Antonyms,     Label { singular: "antonyme",     plural: "antonymes" }),
+    (Lang::De, Section::Antonyms,     Label { singular: "Antonym",      plural: "Antonyme" }),
+    (Lang::Es, Section::Antonyms,     Label { singular: "antónimo",     plural: "antónimos" }),
+
+    (Lang::En, Section::RelatedTerms, Label { singular: "related term", plural: "related terms" }),
+    (Lang::En, Section::DerivedTerms, Label { singular: "derived term", plural: "derived terms" }),
+];
+
+fn label(target: Lang, section: Section) -> Option<Label> {
+    CATALOG
+        .iter()
+        .find(|(lang, sec, _)| *lang == target && *sec == section)
+        .map(|(_, _, label)| *label)
+}
+
+/// Localize a section label for `target`, picking singular/plural by `n`.
+///
+/// The rendered string is count-prefixed (`"3 examples"`, `"1 exemple"`, `"5 例"`). Falls back to
+/// the English entry, then to the section's own key, so an untranslated language still renders.
+pub fn localize(target: Lang, section: Section, n: usize) -> String {
+    let label = label(target, section)
+        .or_else(|| label(Lang::En, section))
+        .unwrap_or(Label {
+            singular: section.key(),
+            plural: section.key(),
+        });
+
+    let form = if n == 1 { label.singular } else { label.plural };
+    format!("{n} {form}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pluralizes_by_count() {
+        assert_eq!(localize(Lang::En, Section::Examples, 1), "1 example");
+        assert_eq!(localize(Lang::En, Section::Examples, 3), "3 examples");
+        assert_eq!(localize(Lang::Fr, Section::Examples, 2), "2 exemples");
+    }
+
+    #[test]
+    fn uncounted_label_forms() {
+        assert_eq!(localize(Lang::Zh, Section::Examples, 4), "4 例");
+        assert_eq!(localize(Lang::Ja, Section::Examples, 1), "1 例");
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        // Russian has no `Synonyms` row -> English.
+        assert_eq!(localize(Lang::Ru, Section::Synonyms, 2), "2 synonyms");
+        // No language translates `RelatedTerms` beyond English; English still resolves.
+        assert_eq!(localize(Lang::Ru, Section::RelatedTerms, 3), "3 related terms");
     }
 }
@@ -1,4 +1,8 @@
-use std::{fs::File, io::BufWriter, sync::LazyLock};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    sync::LazyLock,
+};
 
 use anyhow::Result;
 use regex::Regex;
@@ -10,7 +14,9 @@ use crate::{
     cli::Options,
     dict::{
         Diagnostics, Dictionary, Intermediate, LabelledYomitanEntry,
-        locale::localize_examples_string,
+        inflect::synthesize,
+        locale::{Section, localize},
+        normalize_entry,
     },
     lang::{EditionLang, Lang},
     models::{
@@ -22,8 +28,9 @@ use crate::{
     },
     path::PathManager,
     tags::{
-        REDUNDANT_FORM_TAGS, find_short_pos, find_tag_in_bank, merge_person_tags,
-        remove_redundant_tags, sort_tags, sort_tags_by_similar,
+        canonicalize_usage_labels, find_short_pos, find_tag_in_bank, merge_person_tags,
+        normalize_tags, remove_redundant_tags, sort_tags, sort_tags_by_similar,
+        strip_redundant_form_tags,
     },
     utils::{link_kaikki, link_wiktionary, pretty_println_at_path},
 };
@@ -39,11 +46,60 @@ impl Intermediate for Tidy {
     fn write(&self, pm: &PathManager, options: &Options) -> Result<()> {
         self.write(options, pm)
     }
+
+    fn write_binary(&self, pm: &PathManager) -> Result<()> {
+        let writer_path = crate::dict::core::ir_binary_path(pm);
+        let writer = BufWriter::new(File::create(&writer_path)?);
+        ciborium::into_writer(&TidyBinary::from(self), writer).map_err(|e| anyhow::anyhow!(e))?;
+        if !pm.opts.quiet {
+            pretty_println_at_path("Wrote binary tidy", &writer_path);
+        }
+        Ok(())
+    }
+
+    fn read_binary(pm: &PathManager) -> Result<Self> {
+        let reader = BufReader::new(File::open(crate::dict::core::ir_binary_path(pm))?);
+        let binary: TidyBinary = ciborium::from_reader(reader).map_err(|e| anyhow::anyhow!(e))?;
+        Ok(binary.into())
+    }
+}
+
+/// Round-trippable shadow of [`Tidy`] for the `--reuse-ir` CBOR checkpoint.
+///
+/// `LemmaMap`/`FormMap`'s own `Serialize` impls flatten `LemmaKey`/`FormKey` into nested maps for
+/// human-readable debugging and can't be deserialized back into the original key shape, so the
+/// binary checkpoint round-trips through this plain vec-of-pairs form instead.
+#[derive(Serialize, Deserialize)]
+struct TidyBinary {
+    lemmas: Vec<(LemmaKey, Vec<LemmaInfo>)>,
+    forms: Vec<(FormKey, (FormSource, Vec<String>))>,
+}
+
+impl From<&Tidy> for TidyBinary {
+    fn from(tidy: &Tidy) -> Self {
+        Self {
+            lemmas: tidy.lemma_map.0.clone().into_iter().collect(),
+            forms: tidy.form_map.0.clone().into_iter().collect(),
+        }
+    }
+}
+
+impl From<TidyBinary> for Tidy {
+    fn from(binary: TidyBinary) -> Self {
+        Self {
+            lemma_map: LemmaMap(binary.lemmas.into_iter().collect()),
+            form_map: FormMap(binary.forms.into_iter().collect()),
+        }
+    }
 }
 
 impl Dictionary for DMain {
     type I = Tidy;
 
+    fn streams(&self) -> bool {
+        true
+    }
+
     fn preprocess(
         &self,
         edition: EditionLang,
@@ -67,8 +123,9 @@ impl Dictionary for DMain {
         process_main(edition, source, word_entry, irs);
     }
 
-    fn postprocess(&self, irs: &mut Self::I) {
+    fn postprocess(&self, irs: &mut Self::I, _opts: &Options) {
         postprocess_forms(&mut irs.form_map);
+        postprocess_lemmas(&mut irs.lemma_map);
     }
 
     fn found_ir_message(&self, irs: &Self::I) {
@@ -78,15 +135,29 @@ impl Dictionary for DMain {
         let n_forms_inflection = irs.form_map.len_inflection();
         let n_forms_extracted = irs.form_map.len_extracted();
         let n_forms_alt_of = irs.form_map.len_alt_of();
+        let n_forms_dim = irs.form_map.len_diminutive();
+        let n_forms_aug = irs.form_map.len_augmentative();
+        let n_forms_pej = irs.form_map.len_pejorative();
+        let n_forms_end = irs.form_map.len_endearing();
+        let n_forms_norm = irs.form_map.len_normalized();
         debug_assert_eq!(
             n_forms,
-            n_forms_inflection + n_forms_extracted + n_forms_alt_of,
+            n_forms_inflection
+                + n_forms_extracted
+                + n_forms_alt_of
+                + n_forms_dim
+                + n_forms_aug
+                + n_forms_pej
+                + n_forms_end
+                + n_forms_norm,
             "mismatch in form counts"
         );
         let n_entries = n_lemmas + n_forms;
         println!(
             "Found {n_entries} entries: {n_lemmas} lemmas, {n_forms} forms \
-({n_forms_inflection} inflections, {n_forms_extracted} extracted, {n_forms_alt_of} alt_of)"
+({n_forms_inflection} inflections, {n_forms_extracted} extracted, {n_forms_alt_of} alt_of, \
+{n_forms_dim} diminutives, {n_forms_aug} augmentatives, {n_forms_pej} pejoratives, \
+{n_forms_end} endearing, {n_forms_norm} normalized)"
         );
     }
 
@@ -106,7 +177,7 @@ impl Dictionary for DMain {
         vec![
             (
                 "lemma",
-                to_yomitan_lemmas(edition, options, irs.lemma_map, diagnostics),
+                to_yomitan_lemmas(edition, source, options, irs.lemma_map, diagnostics),
             ),
             ("form", to_yomitan_forms(source, irs.form_map)),
         ]
@@ -117,7 +188,7 @@ impl Dictionary for DMain {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 struct LemmaKey {
     lemma: String,
     reading: String,
@@ -154,9 +225,14 @@ impl LemmaMap {
     fn len(&self) -> usize {
         self.0.values().map(Vec::len).sum()
     }
+
+    /// Whether any lemma entry uses `lemma` as its headword, regardless of reading/pos.
+    fn contains_lemma(&self, lemma: &str) -> bool {
+        self.0.keys().any(|key| key.lemma == lemma)
+    }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 struct FormKey {
     uninflected: String,
     inflected: String,
@@ -240,6 +316,26 @@ impl FormMap {
     fn len_alt_of(&self) -> usize {
         self.len_of(FormSource::AltOf)
     }
+
+    fn len_diminutive(&self) -> usize {
+        self.len_of(FormSource::Diminutive)
+    }
+
+    fn len_augmentative(&self) -> usize {
+        self.len_of(FormSource::Augmentative)
+    }
+
+    fn len_pejorative(&self) -> usize {
+        self.len_of(FormSource::Pejorative)
+    }
+
+    fn len_endearing(&self) -> usize {
+        self.len_of(FormSource::Endearing)
+    }
+
+    fn len_normalized(&self) -> usize {
+        self.len_of(FormSource::Normalized)
+    }
 }
 
 /// Enum used exclusively for debugging. This information doesn't appear on the dictionary.
@@ -250,8 +346,54 @@ enum FormSource {
     Extracted,
     /// Form added via gloss analysis ("is inflection of...")
     Inflection,
-    /// Alternative forms
+    /// Plain alternative form
     AltOf,
+    /// Diminutive derivation
+    Diminutive,
+    /// Augmentative derivation
+    Augmentative,
+    /// Pejorative derivation
+    Pejorative,
+    /// Endearing derivation
+    Endearing,
+    /// Alternative form/spelling detected from a gloss relation marker (cf. [`AltOf`](Self::AltOf),
+    /// which comes from the explicit `alt_of` field instead)
+    AlternativeForm,
+    /// Accent-stripped search alias of a diacritic-rich lemma
+    Normalized,
+}
+
+impl FormSource {
+    /// Classify a non-inflectional relation from its Wiktionary sense/entry tags.
+    ///
+    /// Wiktextract records derivational relations as tags on the sense; anything without a
+    /// recognized derivation tag is treated as a plain alternative form.
+    fn classify_relation(tags: &[Tag]) -> Self {
+        for tag in tags {
+            match tag.as_str() {
+                "diminutive" => return Self::Diminutive,
+                "augmentative" => return Self::Augmentative,
+                "pejorative" => return Self::Pejorative,
+                "endearing" => return Self::Endearing,
+                _ => (),
+            }
+        }
+        Self::AltOf
+    }
+
+    /// Human-readable label emitted on the form entry for relation kinds.
+    const fn relation_label(self) -> &'static str {
+        match self {
+            Self::AltOf => "alternative form of",
+            Self::Diminutive => "diminutive of",
+            Self::Augmentative => "augmentative of",
+            Self::Pejorative => "pejorative of",
+            Self::Endearing => "endearing form of",
+            Self::AlternativeForm => "alternative form/spelling of",
+            // Not a relation kind; only reachable through misuse.
+            Self::Extracted | Self::Inflection | Self::Normalized => "",
+        }
+    }
 }
 
 // NOTE: the less we have here the better. For example, the links could be entirely moved to the
@@ -263,9 +405,27 @@ struct LemmaInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     etymology_text: Option<String>,
 
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    etymology_relations: Vec<EtymologyRelation>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    genders: Vec<Gender>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inflection_class: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    valency_tags: Vec<Tag>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aspect_pair: Option<AspectPair>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     head_info_text: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cross_reference: Option<CrossReference>,
+
     #[serde(rename = "wlink")]
     link_wiktionary: String,
 
@@ -273,6 +433,108 @@ struct LemmaInfo {
     link_kaikki: String,
 }
 
+/// A lemma entry that is itself just a derivational/alternative-form relation to another
+/// headword (cf. [`handle_relational_sense`]), rendered as a backlink rather than a gloss.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CrossReference {
+    target: String,
+    link_wiktionary: String,
+}
+
+/// A Slavic verb's paired aspectual counterpart (cf. [`extract_slavic_verb_info`]), rendered
+/// alongside the preamble as an additional "Aspect pair" details entry rather than replacing the
+/// lemma's own glosses (cf. [`CrossReference`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AspectPair {
+    counterpart: String,
+    link_wiktionary: String,
+}
+
+/// Grammatical gender carried on a headword, as exposed by headword modules (`genders`) in
+/// `word_entry.forms`/`head_templates`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+}
+
+impl Gender {
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "masculine" => Some(Self::Masculine),
+            "feminine" => Some(Self::Feminine),
+            "neuter" => Some(Self::Neuter),
+            _ => None,
+        }
+    }
+
+    /// Compact single-letter code used in the rendered headword line.
+    const fn abbr(self) -> &'static str {
+        match self {
+            Self::Masculine => "m",
+            Self::Feminine => "f",
+            Self::Neuter => "n",
+        }
+    }
+}
+
+/// Kind of etymological relation, mirroring wiktextract's borrowing taxonomy.
+///
+/// Unrecognized templates fall back to the untyped [`BorrowKind::Derived`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum BorrowKind {
+    Borrowed,
+    Inherited,
+    LearnedBorrowing,
+    SemiLearnedBorrowing,
+    Calque,
+    SemanticLoan,
+    Derived,
+    Cognate,
+}
+
+impl BorrowKind {
+    /// Map an etymology template name (`bor`, `inh`, `cal`, …) to a [`BorrowKind`].
+    fn from_template_name(name: &str) -> Self {
+        match name {
+            "bor" | "bor+" | "borrowed" => Self::Borrowed,
+            "inh" | "inh+" | "inherited" => Self::Inherited,
+            "lbor" | "learned borrowing" => Self::LearnedBorrowing,
+            "slbor" | "semi-learned borrowing" => Self::SemiLearnedBorrowing,
+            "cal" | "calque" | "clq" => Self::Calque,
+            "sl" | "semantic loan" => Self::SemanticLoan,
+            "cog" | "cognate" => Self::Cognate,
+            // "der", "derived" and anything unrecognized
+            _ => Self::Derived,
+        }
+    }
+
+    /// Human-readable prefix, completed by the source language (e.g. "Borrowed from").
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Borrowed => "Borrowed from",
+            Self::Inherited => "Inherited from",
+            Self::LearnedBorrowing => "Learned borrowing from",
+            Self::SemiLearnedBorrowing => "Semi-learned borrowing from",
+            Self::Calque => "Calque of",
+            Self::SemanticLoan => "Semantic loan from",
+            Self::Cognate => "Cognate with",
+            Self::Derived => "Derived from",
+        }
+    }
+}
+
+/// A single typed etymological relation pointing at a source-language term.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EtymologyRelation {
+    kind: BorrowKind,
+    source_lang: Lang,
+    term: String,
+}
+
 type GlossTree = Map<String, GlossInfo>;
 
 // ... its really SenseInfo but oh well
@@ -377,6 +639,9 @@ impl Tidy {
 
 fn postprocess_forms(form_map: &mut FormMap) {
     for (_, _, _, _, tags) in form_map.flat_iter_mut() {
+        // Fold usage/register-label synonyms onto their canonical spelling.
+        canonicalize_usage_labels(tags);
+
         // Keep only unique tags and remove tags subsets
         remove_redundant_tags(tags);
 
@@ -394,6 +659,25 @@ fn postprocess_forms(form_map: &mut FormMap) {
     }
 }
 
+/// Canonicalize the usage/register labels carried on every gloss's `tags`/`topics`, folding the
+/// per-edition synonym spellings onto a single form so [`to_yomitan_lemmas`] renders them as
+/// consistent pill labels. Unrecognized tags fall through untouched.
+fn postprocess_lemmas(lemma_map: &mut LemmaMap) {
+    for infos in lemma_map.0.values_mut() {
+        for info in infos {
+            canonicalize_gloss_tree(&mut info.gloss_tree);
+        }
+    }
+}
+
+fn canonicalize_gloss_tree(gloss_tree: &mut GlossTree) {
+    for gloss_info in gloss_tree.values_mut() {
+        canonicalize_usage_labels(&mut gloss_info.tags);
+        canonicalize_usage_labels(&mut gloss_info.topics);
+        canonicalize_gloss_tree(&mut gloss_info.children);
+    }
+}
+
 fn process_main(edition: EditionLang, source: Lang, word_entry: &WordEntry, irs: &mut Tidy) {
     process_forms(edition, source, word_entry, irs);
 
@@ -409,7 +693,44 @@ fn process_main(edition: EditionLang, source: Lang, word_entry: &WordEntry, irs:
         let reading =
             get_reading(edition, source, word_entry).unwrap_or_else(|| word_entry.word.clone());
         irs.insert_lemma(&word_entry.word, &reading, &word_entry.pos, raw_sense_entry);
+
+        process_search_aliases(source, word_entry, irs);
+    }
+}
+
+/// For sources whose canonical form carries length/stress diacritics (Latin, Russian, Ancient
+/// Greek), add an accent-free search alias pointing back to the lemma, so the deinflector can
+/// resolve undecorated input (`fama` → `fāma`) while the display form keeps its marks.
+fn process_search_aliases(source: Lang, word_entry: &WordEntry, irs: &mut Tidy) {
+    let Some(canonical) = get_canonical_word(source, word_entry) else {
+        return;
+    };
+
+    let stripped = normalize_orthography(source, &canonical);
+
+    // Nothing to alias if there were no marks to strip, or the bare form is already the lemma.
+    if stripped == canonical || stripped == word_entry.word {
+        return;
     }
+
+    // If the lemma's own headword carries marks too, `to_yomitan_lemma` already strips it and
+    // exposes the bare form as the search term directly; don't add a redundant alias for it.
+    if normalize_orthography(source, &word_entry.word) != word_entry.word {
+        return;
+    }
+
+    // Don't shadow a genuinely distinct headword that already owns the bare spelling.
+    if irs.lemma_map.contains_lemma(&stripped) {
+        return;
+    }
+
+    irs.insert_form(
+        &word_entry.word,
+        &stripped,
+        &word_entry.pos,
+        FormSource::Normalized,
+        vec!["alternative spelling".to_string()],
+    );
 }
 
 // Everything that mutates word_entry
@@ -420,6 +741,8 @@ fn preprocess_main(
     word_entry: &mut WordEntry,
     irs: &mut Tidy,
 ) {
+    normalize_entry(word_entry, options.normalization);
+
     // WARN: mutates word_entry::senses::sense::tags
     match edition {
         EditionLang::En => {
@@ -490,8 +813,10 @@ fn preprocess_main(
     let old_senses = std::mem::take(&mut word_entry.senses);
     let mut senses_without_inflections = Vec::new();
     for sense in old_senses {
-        if is_inflection_sense(edition, &sense)
-            && (!options.experimental || word_entry.non_trivial_forms().next().is_none())
+        if let Some(kind) = is_relational_sense(edition, &sense) {
+            handle_relational_sense(edition, source, word_entry, &sense, kind, irs);
+        } else if is_inflection_sense(edition, &sense)
+            && (!options.experimental || word_entry.non_trivial_forms(edition).next().is_none())
         {
             handle_inflection_sense(edition, source, word_entry, &sense, irs);
         } else {
@@ -500,6 +825,10 @@ fn preprocess_main(
     }
     word_entry.senses = senses_without_inflections;
 
+    if options.synthesize_inflections {
+        process_synthesized_forms(edition, source, word_entry, irs);
+    }
+
     // WARN: mutates word_entry::senses::glosses
     //
     // rg: full stop
@@ -520,17 +849,16 @@ fn preprocess_main(
 
 /// Add Extracted forms. That is, forms from `word_entry.forms`.
 fn process_forms(edition: EditionLang, source: Lang, word_entry: &WordEntry, irs: &mut Tidy) {
-    for form in word_entry.non_trivial_forms() {
-        let filtered_tags: Vec<_> = form
-            .tags
-            .iter()
-            .map(String::as_str)
-            .filter(|tag| !REDUNDANT_FORM_TAGS.contains(tag))
-            .collect();
+    for form in word_entry.non_trivial_forms(edition) {
+        let mut filtered_tags: Vec<String> = form.tags.clone();
+        strip_redundant_form_tags(&mut filtered_tags);
         if filtered_tags.is_empty() {
             continue;
         }
 
+        // Expand any compact edition grammemes onto canonical tags before tidy.
+        normalize_tags(edition, &mut filtered_tags);
+
         if should_break_at_finish_forms(edition, source, form) {
             break;
         }
@@ -545,6 +873,48 @@ fn process_forms(edition: EditionLang, source: Lang, word_entry: &WordEntry, irs
     }
 }
 
+/// Algorithmically generate deinflections for a lemma whose forms table is sparse or empty,
+/// via the [`synthesize`] engine. Only steps in when wiktextract gave us nothing real to work with,
+/// so a genuine forms table always wins.
+fn process_synthesized_forms(
+    edition: EditionLang,
+    source: Lang,
+    word_entry: &WordEntry,
+    irs: &mut Tidy,
+) {
+    if word_entry.non_trivial_forms(edition).next().is_some() {
+        return;
+    }
+
+    let Some(class_id) = synthesis_class_id(source, word_entry) else {
+        return;
+    };
+
+    for (surface, tags) in synthesize(&word_entry.word, class_id, None) {
+        irs.insert_form(
+            &word_entry.word,
+            &surface,
+            &word_entry.pos,
+            FormSource::Inflection,
+            tags,
+        );
+    }
+}
+
+/// Map a source language/`word_entry` to the [`synthesize`] class it should be generated from, if
+/// any. Only Latin 1st-declension nouns are wired up for now; other classes (cf. `inflect::CLASSES`)
+/// are shipped for direct use but have no trigger in this tree yet.
+fn synthesis_class_id(source: Lang, word_entry: &WordEntry) -> Option<&'static str> {
+    match source {
+        Lang::La if word_entry.pos == "noun" => word_entry
+            .tags
+            .iter()
+            .any(|tag| tag == "first-declension")
+            .then_some("la-1st-declension"),
+        _ => None,
+    }
+}
+
 // Finnish from the English edition crashes with out-of-memory.
 // There are simply too many forms, so we prune the less used (possessive).
 //
@@ -566,30 +936,33 @@ fn should_break_at_finish_forms(edition: EditionLang, source: Lang, form: &Form)
     false
 }
 
-/// Add `AltOf` forms. That is, alternative forms.
+/// Add non-inflectional relation forms, classified into alternative / diminutive /
+/// augmentative / pejorative / endearing from the Wiktionary tags.
 fn process_alt_forms(word_entry: &WordEntry, irs: &mut Tidy) {
-    let base_tags = vec!["alt-of".to_string()];
+    let entry_kind = FormSource::classify_relation(&word_entry.tags);
 
     for alt_form in &word_entry.alt_of {
         irs.insert_form(
             &word_entry.word,
             &alt_form.word,
             &word_entry.pos,
-            FormSource::AltOf,
-            base_tags.clone(),
+            entry_kind,
+            vec![entry_kind.relation_label().to_string()],
         );
     }
 
     for sense in &word_entry.senses {
+        let kind = FormSource::classify_relation(&sense.tags);
+
         let mut sense_tags = sense.tags.clone();
-        sense_tags.extend(base_tags.clone());
+        sense_tags.push(kind.relation_label().to_string());
 
         for alt_form in &sense.alt_of {
             irs.insert_form(
                 &word_entry.word,
                 &alt_form.word,
                 &word_entry.pos,
-                FormSource::AltOf,
+                kind,
                 sense_tags.clone(),
             );
         }
@@ -724,16 +1097,197 @@ fn process_word_entry(
         .etymology_texts()
         .map(|etymology_text| etymology_text.join("\n"));
 
+    let (genders, inflection_class) = extract_headword_grammar(edition, source, word_entry);
+    let (valency_tags, aspect_pair) = extract_slavic_verb_info(edition, source, word_entry);
+
     Some(LemmaInfo {
         gloss_tree,
         etymology_text,
+        etymology_relations: get_etymology_relations(word_entry),
+        genders,
+        inflection_class,
+        valency_tags,
+        aspect_pair,
         head_info_text: get_head_info(&word_entry.head_templates)
             .map(std::string::ToString::to_string),
+        cross_reference: None,
         link_wiktionary: link_wiktionary(edition, source, &word_entry.word),
         link_kaikki: link_kaikki(edition, source, &word_entry.word),
     })
 }
 
+/// Transitivity/aspect-exclusivity/animacy labels and the paired aspectual counterpart verb
+/// carried on Slavic-edition verb entries, keyed by `edition`. Starts Russian-only.
+///
+/// The labels live on `word_entry.tags` (cf. the same field read by
+/// [`extract_latin_inflection_class`]); the counterpart comes from the matching tagged form (cf.
+/// [`WordEntry::canonical_form`]) via [`WordEntry::aspect_pair_form`].
+fn extract_slavic_verb_info(
+    edition: EditionLang,
+    source: Lang,
+    word_entry: &WordEntry,
+) -> (Vec<Tag>, Option<AspectPair>) {
+    if !matches!(edition, EditionLang::Ru) {
+        return (Vec::new(), None);
+    }
+
+    let valency_tags = word_entry
+        .tags
+        .iter()
+        .filter_map(|tag| {
+            SLAVIC_VERB_LABELS
+                .iter()
+                .find(|(raw, _)| raw == tag)
+                .map(|(_, short)| (*short).to_string())
+        })
+        .collect();
+
+    let aspect_pair = word_entry.aspect_pair_form().map(|form| AspectPair {
+        counterpart: form.form.clone(),
+        link_wiktionary: link_wiktionary(edition, source, &form.form),
+    });
+
+    (valency_tags, aspect_pair)
+}
+
+/// Recurring Slavic verb grammar labels, mapped to the short form shown in the term's tag column.
+const SLAVIC_VERB_LABELS: [(&str, &str); 7] = [
+    ("transitive", "tr"),
+    ("intransitive", "intr"),
+    ("imperfective-only", "impf-only"),
+    ("perfective-only", "pf-only"),
+    ("impersonal", "impers"),
+    ("animate", "anim"),
+    ("inanimate", "inan"),
+];
+
+/// Structured headword grammar (gender, noun declension / verb conjugation class), keyed by
+/// `(edition, source)`.
+///
+/// Generalizes the per-edition headword hacks scattered across this module (Greek gender
+/// scraped from the matching inflection-table form, Latin declension/conjugation tags) into one
+/// place, so [`to_yomitan_lemma`] can render a single compact headword line above the gloss tree.
+fn extract_headword_grammar(
+    edition: EditionLang,
+    source: Lang,
+    word_entry: &WordEntry,
+) -> (Vec<Gender>, Option<String>) {
+    let genders = match edition {
+        // Greek doesn't expose gender on head_templates; it shows up as a tag on the form that
+        // matches the headword itself (cf. the same lookup in preprocess_main).
+        EditionLang::El => {
+            let mut genders = Vec::new();
+            for form in &word_entry.forms {
+                if form.form != word_entry.word {
+                    continue;
+                }
+                for tag in &form.tags {
+                    if let Some(gender) = Gender::from_tag(tag)
+                        && !genders.contains(&gender)
+                    {
+                        genders.push(gender);
+                    }
+                }
+            }
+            genders
+        }
+        _ => Vec::new(),
+    };
+
+    let inflection_class = match source {
+        Lang::La => extract_latin_inflection_class(word_entry),
+        _ => None,
+    };
+
+    (genders, inflection_class)
+}
+
+const LATIN_NOUN_DECLENSIONS: [(&str, &str); 5] = [
+    ("first-declension", "1st declension"),
+    ("second-declension", "2nd declension"),
+    ("third-declension", "3rd declension"),
+    ("fourth-declension", "4th declension"),
+    ("fifth-declension", "5th declension"),
+];
+
+const LATIN_VERB_CONJUGATIONS: [(&str, &str); 4] = [
+    ("first-conjugation", "1st conjugation"),
+    ("second-conjugation", "2nd conjugation"),
+    ("third-conjugation", "3rd conjugation"),
+    ("fourth-conjugation", "4th conjugation"),
+];
+
+/// Latin declension/conjugation class, as exposed on `word_entry.tags` (cf. the Russian
+/// `word_entry.tags` → `sense.tags` propagation in `preprocess_main`, which reads the same field).
+fn extract_latin_inflection_class(word_entry: &WordEntry) -> Option<String> {
+    let table = if word_entry.pos == "verb" {
+        &LATIN_VERB_CONJUGATIONS[..]
+    } else {
+        &LATIN_NOUN_DECLENSIONS[..]
+    };
+
+    word_entry.tags.iter().find_map(|tag| {
+        table
+            .iter()
+            .find(|(raw, _)| *raw == tag)
+            .map(|(_, label)| (*label).to_string())
+    })
+}
+
+/// Render the compact headword grammar line (e.g. `"m, 1st declension"`), if there's anything to
+/// show.
+fn headword_grammar_line(genders: &[Gender], inflection_class: Option<&str>) -> Option<String> {
+    let gender_label = match genders {
+        [] => None,
+        genders => Some(
+            genders
+                .iter()
+                .map(|gender| gender.abbr())
+                .collect::<Vec<_>>()
+                .join("/"),
+        ),
+    };
+
+    match (gender_label, inflection_class) {
+        (None, None) => None,
+        (Some(gender_label), None) => Some(gender_label),
+        (None, Some(inflection_class)) => Some(inflection_class.to_string()),
+        (Some(gender_label), Some(inflection_class)) => {
+            Some(format!("{gender_label}, {inflection_class}"))
+        }
+    }
+}
+
+// rg: getetymologyrelations
+/// Walk the entry's etymology templates and build a typed list of relations.
+///
+/// Templates whose source-language argument does not resolve to a supported [`Lang`]
+/// are skipped, since we have no target page to link them to.
+fn get_etymology_relations(word_entry: &WordEntry) -> Vec<EtymologyRelation> {
+    let mut relations = Vec::new();
+
+    for template in word_entry.etymology_templates() {
+        let kind = BorrowKind::from_template_name(&template.name);
+
+        // Positional args follow wiktextract: "1" is the target language, "2" the source
+        // language and "3" the source term.
+        let Some(source_lang) = template.args.get("2").and_then(|code| code.parse().ok()) else {
+            continue;
+        };
+        let Some(term) = template.args.get("3").filter(|term| !term.is_empty()) else {
+            continue;
+        };
+
+        relations.push(EtymologyRelation {
+            kind,
+            source_lang,
+            term: term.clone(),
+        });
+    }
+
+    relations
+}
+
 // default version getphonetictranscription
 pub fn get_ipas(word_entry: &WordEntry) -> Vec<Ipa> {
     let ipas_iter = word_entry.sounds.iter().filter_map(|sound| {
@@ -851,6 +1405,88 @@ static DE_INFLECTION_RE: LazyLock<Regex> = LazyLock::new(|| {
     ).unwrap()
 });
 
+/// Per-edition table mapping a gloss's leading relation marker to the [`FormSource`] it denotes.
+///
+/// Mirrors the shorthand used in entry-authoring conventions (`dim:`, `aug:`, `pej:`, `end:`,
+/// `altof:`); add an edition's own markers here to pick them up in [`is_relational_sense`].
+fn relation_markers(edition: EditionLang) -> &'static [(&'static str, FormSource)] {
+    match edition {
+        EditionLang::En => &[
+            ("dim:", FormSource::Diminutive),
+            ("aug:", FormSource::Augmentative),
+            ("pej:", FormSource::Pejorative),
+            ("end:", FormSource::Endearing),
+            ("altof:", FormSource::AlternativeForm),
+        ],
+        _ => &[],
+    }
+}
+
+/// Detect a sense that marks a derivational/alternative-form *relation* rather than a true
+/// inflection.
+///
+/// Unlike [`is_inflection_sense`], a "diminutive of X" or "alternative form of X" is a headword
+/// in its own right (it has its own meaning, register, etc.), so [`handle_relational_sense`] gives
+/// it a real lemma entry with a cross-reference backlink, instead of funnelling it through
+/// [`handle_inflection_sense`]'s causal conjugation chain.
+fn is_relational_sense(edition: EditionLang, sense: &Sense) -> Option<FormSource> {
+    if sense.form_of.is_empty() {
+        return None;
+    }
+
+    relation_markers(edition).iter().find_map(|(marker, kind)| {
+        sense
+            .glosses
+            .iter()
+            .any(|gloss| gloss.starts_with(marker))
+            .then_some(*kind)
+    })
+}
+
+/// Insert a lemma entry for a relational sense (cf. [`is_relational_sense`]): a one-gloss headword
+/// whose definition is just the relation label, with a [`CrossReference`] back to the base word
+/// that [`to_yomitan_lemma`] renders as a linked backlink instead of a gloss tree.
+fn handle_relational_sense(
+    edition: EditionLang,
+    source: Lang,
+    word_entry: &WordEntry,
+    sense: &Sense,
+    kind: FormSource,
+    irs: &mut Tidy,
+) {
+    let Some(target) = sense.form_of.first().filter(|form| !form.word.is_empty()) else {
+        return;
+    };
+
+    let mut gloss_tree = GlossTree::default();
+    gloss_tree.insert(kind.relation_label().to_string(), GlossInfo::default());
+
+    let reading =
+        get_reading(edition, source, word_entry).unwrap_or_else(|| word_entry.word.clone());
+
+    irs.insert_lemma(
+        &word_entry.word,
+        &reading,
+        &word_entry.pos,
+        LemmaInfo {
+            gloss_tree,
+            etymology_text: None,
+            etymology_relations: Vec::new(),
+            genders: Vec::new(),
+            inflection_class: None,
+            valency_tags: Vec::new(),
+            aspect_pair: None,
+            head_info_text: None,
+            cross_reference: Some(CrossReference {
+                target: target.word.clone(),
+                link_wiktionary: link_wiktionary(edition, source, &target.word),
+            }),
+            link_wiktionary: link_wiktionary(edition, source, &word_entry.word),
+            link_kaikki: link_kaikki(edition, source, &word_entry.word),
+        },
+    );
+}
+
 // rg: isinflectiongloss
 fn is_inflection_sense(edition: EditionLang, sense: &Sense) -> bool {
     match edition {
@@ -1032,27 +1668,52 @@ fn handle_inflection_sense_en(source: Lang, word_entry: &WordEntry, sense: &Sens
     }
 }
 
-fn normalize_orthography(source: Lang, word: &str) -> String {
+/// Per-`Lang` accent profile: combining marks that decorate the canonical/display spelling
+/// (vowel length, stress, polytonic breathing/accent) but shouldn't be required to type when
+/// searching. Mirrors how headword/declension modules track stress (acute/circumflex) and
+/// length (macron/breve) separately from the base orthography.
+///
+/// Used by both [`normalize_orthography`] (inflected forms) and [`process_search_aliases`]
+/// (lemma search aliases), so the two stay in sync.
+fn accent_profile(source: Lang) -> &'static [char] {
     match source {
-        Lang::Grc | Lang::La | Lang::Ru => {
-            // Normalize to NFD and drop combining accents
-            word.nfd()
-                .filter(|c| !('\u{0300}'..='\u{036F}').contains(c))
-                .collect()
-        }
-        _ => word.to_string(),
+        // Vowel length (macron, breve), diaeresis and the tie bar used for diphthongs.
+        Lang::La => &['\u{0304}', '\u{0306}', '\u{0308}', '\u{0361}'],
+        // Stress mark only; length/diaeresis don't apply to Cyrillic orthography.
+        Lang::Ru | Lang::Uk => &['\u{0301}'],
+        // Polytonic diacritics: acute/grave/circumflex accent, smooth/rough breathing, iota
+        // subscript, vowel length, diaeresis and the tie bar used for diphthongs.
+        Lang::Grc => &[
+            '\u{0301}', '\u{0300}', '\u{0342}', '\u{0313}', '\u{0314}', '\u{0345}', '\u{0304}',
+            '\u{0306}', '\u{0308}', '\u{0361}',
+        ],
+        _ => &[],
+    }
+}
+
+fn normalize_orthography(source: Lang, word: &str) -> String {
+    let marks = accent_profile(source);
+    if marks.is_empty() {
+        return word.to_string();
     }
+
+    word.nfd().filter(|c| !marks.contains(c)).nfc().collect()
 }
 
 #[tracing::instrument(skip_all)]
 fn to_yomitan_lemmas(
     edition: EditionLang,
+    source: Lang,
     options: &Options,
     lemma_map: LemmaMap,
     diagnostics: &mut Diagnostics,
 ) -> Vec<YomitanEntry> {
     let mut yomitan_entries = Vec::new();
 
+    // Set of known headwords, used to turn etymology relations into internal
+    // cross-references when their target term is part of this dictionary.
+    let lemma_set: Set<String> = lemma_map.0.keys().map(|key| key.lemma.clone()).collect();
+
     for (key, infos) in lemma_map.0 {
         let LemmaKey {
             lemma,
@@ -1061,7 +1722,9 @@ fn to_yomitan_lemmas(
         } = key;
 
         yomitan_entries.extend(infos.into_iter().map(|info| {
-            to_yomitan_lemma(edition, options, &lemma, &reading, &pos, info, diagnostics)
+            to_yomitan_lemma(
+                edition, source, options, &lemma, &reading, &pos, info, &lemma_set, diagnostics,
+            )
         }));
     }
 
@@ -1071,11 +1734,13 @@ fn to_yomitan_lemmas(
 // TODO: consume info
 fn to_yomitan_lemma(
     edition: EditionLang,
+    source: Lang,
     options: &Options,
     lemma: &str,
     reading: &str,
     pos: &Pos, // should be &str
     info: LemmaInfo,
+    lemma_set: &Set<String>,
     diagnostics: &mut Diagnostics,
 ) -> YomitanEntry {
     let found_pos = match find_short_pos(pos) {
@@ -1083,20 +1748,52 @@ fn to_yomitan_lemma(
         None => pos.clone(),
     };
 
-    let yomitan_reading = if *reading == *lemma { "" } else { reading };
+    // If the lemma itself still carries length/stress diacritics, expose the accent-stripped
+    // form as the searchable term and fall back to the full spelling as the reading, the same
+    // way to_yomitan_forms does for inflected forms.
+    let search_form = normalize_orthography(source, lemma);
+    let (term, yomitan_reading) = if search_form == *lemma {
+        let reading = if *reading == *lemma { "" } else { reading };
+        (lemma.to_string(), reading.to_string())
+    } else {
+        (search_form, lemma.to_string())
+    };
 
-    let common_short_tags_found =
+    let mut common_short_tags_found =
         get_found_tags(options, lemma, pos, &info.gloss_tree, diagnostics);
+    common_short_tags_found.extend(info.valency_tags);
 
     let mut detailed_definition_content = Node::new_array();
 
-    if info.etymology_text.is_some() || info.head_info_text.is_some() {
+    if let Some(grammar_line) =
+        headword_grammar_line(&info.genders, info.inflection_class.as_deref())
+    {
+        detailed_definition_content.push(wrap(
+            NTag::Div,
+            "headword-grammar",
+            Node::Text(grammar_line),
+        ));
+    }
+
+    if info.etymology_text.is_some()
+        || info.head_info_text.is_some()
+        || !info.etymology_relations.is_empty()
+        || info.aspect_pair.is_some()
+    {
         detailed_definition_content.push(structured_preamble(
+            edition,
             info.etymology_text,
+            info.etymology_relations,
             info.head_info_text,
+            info.aspect_pair,
+            lemma_set,
         ));
     }
 
+    if let Some(cross_reference) = info.cross_reference {
+        detailed_definition_content.push(structured_cross_reference(cross_reference, lemma_set));
+    }
+
     detailed_definition_content.push(structured_glosses(
         edition,
         info.gloss_tree,
@@ -1106,8 +1803,8 @@ fn to_yomitan_lemma(
     detailed_definition_content.push(structured_backlink(info.link_wiktionary, info.link_kaikki));
 
     YomitanEntry::TermBank(TermBank(
-        lemma.to_string(),
-        yomitan_reading.to_string(),
+        term,
+        yomitan_reading,
         common_short_tags_found.join(" "),
         found_pos,
         vec![DetailedDefinition::structured(detailed_definition_content)],
@@ -1155,17 +1852,28 @@ fn get_found_tags(
 }
 
 fn build_details_entry(ty: &str, content: String) -> Node {
+    build_details_node(ty, Node::Text(content))
+}
+
+fn build_details_node(ty: &str, content: Node) -> Node {
     wrap(
         NTag::Details,
         &format!("details-entry-{ty}"),
         Node::Array(vec![
             wrap(NTag::Summary, "summary-entry", Node::Text(ty.into())),
-            wrap(NTag::Div, &format!("{ty}-content"), Node::Text(content)),
+            wrap(NTag::Div, &format!("{ty}-content"), content),
         ]),
     )
 }
 
-fn structured_preamble(etymology_text: Option<String>, head_info_text: Option<String>) -> Node {
+fn structured_preamble(
+    edition: EditionLang,
+    etymology_text: Option<String>,
+    etymology_relations: Vec<EtymologyRelation>,
+    head_info_text: Option<String>,
+    aspect_pair: Option<AspectPair>,
+    lemma_set: &Set<String>,
+) -> Node {
     let mut preamble_content = Node::new_array();
     if let Some(head_info_text) = head_info_text {
         preamble_content.push(build_details_entry("Grammar", head_info_text));
@@ -1173,6 +1881,16 @@ fn structured_preamble(etymology_text: Option<String>, head_info_text: Option<St
     if let Some(etymology_text) = etymology_text {
         preamble_content.push(build_details_entry("Etymology", etymology_text));
     }
+    if !etymology_relations.is_empty() {
+        preamble_content.push(structured_etymology_relations(
+            edition,
+            etymology_relations,
+            lemma_set,
+        ));
+    }
+    if let Some(aspect_pair) = aspect_pair {
+        preamble_content.push(structured_aspect_pair(aspect_pair, lemma_set));
+    }
 
     wrap(
         NTag::Div,
@@ -1181,6 +1899,87 @@ fn structured_preamble(etymology_text: Option<String>, head_info_text: Option<St
     )
 }
 
+/// Render the Russian-edition aspectual counterpart as a labelled, linked "Aspect pair" details
+/// entry (cf. [`structured_cross_reference`] for the analogous non-gloss lemma case).
+fn structured_aspect_pair(aspect_pair: AspectPair, lemma_set: &Set<String>) -> Node {
+    let mut content = vec![Node::Backlink(BacklinkContent::with_text(
+        aspect_pair.link_wiktionary,
+        aspect_pair.counterpart.clone(),
+    ))];
+
+    if lemma_set.contains(&aspect_pair.counterpart) {
+        content.push(Node::Text(" ".into()));
+        content.push(Node::Backlink(BacklinkContent::with_text(
+            format!("?query={}&wildcards=off", aspect_pair.counterpart),
+            "→".into(),
+        )));
+    }
+
+    build_details_node("Aspect pair", Node::Array(content))
+}
+
+/// Render the typed etymology relations as a list of labelled, linked entries.
+///
+/// Each term links out to its Wiktionary page, and — when the term is itself a headword
+/// of this dictionary — also to an internal Yomitan cross-reference (`?query=`) so users
+/// can hover-navigate the word's ancestry.
+fn structured_etymology_relations(
+    edition: EditionLang,
+    relations: Vec<EtymologyRelation>,
+    lemma_set: &Set<String>,
+) -> Node {
+    let items = relations
+        .into_iter()
+        .map(|relation| {
+            let label = format!("{} {}: ", relation.kind.label(), relation.source_lang.long());
+
+            let mut line = Node::Array(vec![
+                Node::Text(label),
+                Node::Backlink(BacklinkContent::with_text(
+                    link_wiktionary(edition, relation.source_lang, &relation.term),
+                    relation.term.clone(),
+                )),
+            ]);
+
+            if lemma_set.contains(&relation.term) {
+                line.push(Node::Text(" ".into()));
+                line.push(Node::Backlink(BacklinkContent::with_text(
+                    format!("?query={}&wildcards=off", relation.term),
+                    "→".into(),
+                )));
+            }
+
+            wrap(NTag::Li, "", line)
+        })
+        .collect();
+
+    build_details_node("Relations", wrap(NTag::Ul, "relations", Node::Array(items)))
+}
+
+/// Render a [`CrossReference`] (cf. [`handle_relational_sense`]) as a linked "→ target" line.
+///
+/// Like [`structured_etymology_relations`], the target also gets an internal Yomitan
+/// cross-reference (`?query=`) when it's itself a headword of this dictionary.
+fn structured_cross_reference(cross_reference: CrossReference, lemma_set: &Set<String>) -> Node {
+    let mut content = vec![
+        Node::Text("→ ".into()),
+        Node::Backlink(BacklinkContent::with_text(
+            cross_reference.link_wiktionary,
+            cross_reference.target.clone(),
+        )),
+    ];
+
+    if lemma_set.contains(&cross_reference.target) {
+        content.push(Node::Text(" ".into()));
+        content.push(Node::Backlink(BacklinkContent::with_text(
+            format!("?query={}&wildcards=off", cross_reference.target),
+            "→".into(),
+        )));
+    }
+
+    wrap(NTag::Div, "cross-reference", Node::Array(content))
+}
+
 fn structured_backlink(wlink: String, klink: String) -> Node {
     wrap(
         NTag::Div,
@@ -1293,6 +2092,9 @@ fn structured_tags(tags: &[Tag], common_short_tags_found: &[Tag]) -> Option<Node
                             ("content", "tag"),
                             ("category", &tag_info.category),
                         ])),
+                        style: None,
+                        col_span: None,
+                        row_span: None,
                         content: Node::Text(tag_info.short_tag),
                     }
                     .into_node(),
@@ -1320,7 +2122,7 @@ fn structured_examples(edition: EditionLang, examples: &[Example]) -> Node {
     let mut structured_examples_content = wrap(
         NTag::Summary,
         "summary-entry",
-        Node::Text(localize_examples_string(edition, examples.len())),
+        Node::Text(localize(edition.into(), Section::Examples, examples.len())),
     )
     .into_array_node();
 
@@ -0,0 +1,170 @@
+//! Tolerant near-duplicate merging for the merged dictionaries.
+//!
+//! Combining entries across editions produces many headwords that differ only by diacritics,
+//! casing or a one-character slip. The exact-key merge in each `postprocess` keeps those apart;
+//! this pass first buckets entries by a folded key (see [`crate::cli::FoldLevel`]), then within a
+//! bucket treats two surfaces as the same headword when their Damerau–Levenshtein distance is within
+//! a threshold, folding the rest into the canonical (shortest) form.
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::cli::FoldLevel;
+
+/// Combining diacritical marks block (U+0300–U+036F), stripped at [`FoldLevel::Diacritics`].
+const COMBINING_MARKS: std::ops::RangeInclusive<char> = '\u{0300}'..='\u{036f}';
+
+/// Fold a surface form to the key used for bucketing, per `fold`.
+pub fn normalize_key(surface: &str, fold: FoldLevel) -> String {
+    let nfkc: String = surface.nfkc().collect();
+    match fold {
+        FoldLevel::None => nfkc,
+        FoldLevel::Case => nfkc.to_lowercase(),
+        FoldLevel::Diacritics => nfkc
+            .to_lowercase()
+            .nfd()
+            .filter(|c| !COMBINING_MARKS.contains(c))
+            .collect(),
+    }
+}
+
+/// Damerau–Levenshtein (optimal string alignment) distance, bounded by `max`.
+///
+/// Returns `Some(distance)` when the two strings are within `max` edits — a substitution, an
+/// insertion, a deletion or a transposition of adjacent characters each count as one — and `None`
+/// once the running row minimum proves the distance must exceed `max`, so most comparisons abort
+/// after a couple of rows.
+pub fn bounded_damerau_levenshtein(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut prev2 = vec![0usize; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        let mut row_min = curr[0];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut val = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                val = val.min(prev2[j - 2] + 1);
+            }
+            curr[j] = val;
+            row_min = row_min.min(val);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    let distance = prev[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+/// Collapse near-duplicate items, keyed and compared by their surface form.
+///
+/// `surface` reads the headword an item sorts under; `combine(canonical, other)` folds a variant's
+/// payload into the retained entry. Within a fold bucket, entries whose surfaces are within
+/// `max_distance` are clustered and reduced to their shortest surface, so the canonical entry
+/// absorbs the variants' data while the duplicates disappear.
+pub fn tolerant_merge<T>(
+    items: Vec<T>,
+    max_distance: usize,
+    fold: FoldLevel,
+    surface: impl Fn(&T) -> &str,
+    combine: impl Fn(&mut T, T),
+) -> Vec<T> {
+    // Preserve first-seen order of buckets and of clusters within them.
+    let mut buckets: crate::Map<String, Vec<Vec<T>>> = crate::Map::default();
+
+    for item in items {
+        let key = normalize_key(surface(&item), fold);
+        let clusters = buckets.entry(key).or_default();
+        let slot = clusters.iter_mut().find(|cluster| {
+            bounded_damerau_levenshtein(surface(&cluster[0]), surface(&item), max_distance).is_some()
+        });
+        match slot {
+            Some(cluster) => cluster.push(item),
+            None => clusters.push(vec![item]),
+        }
+    }
+
+    let mut out = Vec::new();
+    for (_, clusters) in buckets {
+        for cluster in clusters {
+            // Canonical = shortest surface, ties broken by first-seen order.
+            let canon = cluster
+                .iter()
+                .enumerate()
+                .min_by_key(|(idx, item)| (surface(item).chars().count(), *idx))
+                .map(|(idx, _)| idx)
+                .unwrap_or(0);
+
+            let mut members = cluster.into_iter();
+            let mut canonical = None;
+            let mut rest = Vec::new();
+            for (idx, item) in (&mut members).enumerate() {
+                if idx == canon {
+                    canonical = Some(item);
+                } else {
+                    rest.push(item);
+                }
+            }
+
+            if let Some(mut canonical) = canonical {
+                for other in rest {
+                    combine(&mut canonical, other);
+                }
+                out.push(canonical);
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_distance_counts_a_transposition_as_one() {
+        assert_eq!(bounded_damerau_levenshtein("ab", "ba", 1), Some(1));
+        assert_eq!(bounded_damerau_levenshtein("color", "colour", 1), Some(1));
+        assert_eq!(bounded_damerau_levenshtein("kitten", "sitting", 1), None);
+    }
+
+    #[test]
+    fn normalize_key_folds_case_and_diacritics() {
+        assert_eq!(normalize_key("Café", FoldLevel::Diacritics), "cafe");
+        assert_eq!(normalize_key("Café", FoldLevel::Case), "café");
+        assert_eq!(normalize_key("Café", FoldLevel::None), "Café");
+    }
+
+    #[test]
+    fn merges_near_duplicates_into_shortest_surface() {
+        let items = vec![
+            ("colour".to_string(), vec!["a"]),
+            ("color".to_string(), vec!["b"]),
+            ("apple".to_string(), vec!["c"]),
+        ];
+        let mut merged = tolerant_merge(
+            items,
+            1,
+            FoldLevel::Diacritics,
+            |(word, _)| word.as_str(),
+            |canonical, other| canonical.1.extend(other.1),
+        );
+        merged.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(merged.len(), 2);
+        let color = merged.iter().find(|(w, _)| w == "color").unwrap();
+        assert_eq!(color.1.len(), 2); // colour folded in
+    }
+}
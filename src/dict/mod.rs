@@ -1,10 +1,16 @@
 mod core;
+mod diff;
 mod index;
+mod inflect;
 mod locale;
 mod main;
+mod merge;
 mod other;
+mod tidy;
 mod writer;
 
 pub use core::*;
+pub use diff::*;
 pub use main::*;
 pub use other::*;
+pub use tidy::*;
@@ -1,7 +1,9 @@
 use crate::{
     Map, Set,
-    cli::Options,
-    dict::{Diagnostics, Dictionary, LabelledYomitanEntry, get_ipas, get_reading},
+    cli::{NormalizationForm, Options},
+    dict::{
+        Diagnostics, Dictionary, LabelledYomitanEntry, get_ipas, get_reading, merge::tolerant_merge,
+    },
     lang::{EditionLang, Lang},
     models::{
         kaikki::WordEntry,
@@ -10,7 +12,7 @@ use crate::{
             TermPhoneticTranscription, YomitanEntry, wrap,
         },
     },
-    tags::find_short_pos,
+    tags::{find_short_pos, strip_redundant_form_tags},
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -25,9 +27,46 @@ pub struct DIpa;
 #[derive(Debug, Clone, Copy)]
 pub struct DIpaMerged;
 
+/// Hyphenation dictionary: emits Knuth-Liang syllable breaks for each headword as structured
+/// content (e.g. `hy·phen·ation`), so learners can see where long words break.
+#[derive(Debug, Clone, Copy)]
+pub struct DHyphenation;
+
+/// Form-of dictionary: maps inflected surface forms back to their lemma so that a lookup on a
+/// conjugated/declined word resolves to its base entry.
+#[derive(Debug, Clone, Copy)]
+pub struct DFormOf;
+
+/// Deinflection dictionary built straight from the `forms` inflection tables: each surface form
+/// carries its base lemma and the tags describing the inflection.
+#[derive(Debug, Clone, Copy)]
+pub struct DForms;
+
+/// Translation-matrix glossary: one dictionary covering several target languages at once, with the
+/// translations grouped by `lang_code` per headword.
+#[derive(Debug, Clone, Copy)]
+pub struct DGlossaryMatrix;
+
 impl Dictionary for DGlossary {
     type I = Vec<YomitanEntry>;
 
+    fn streams(&self) -> bool {
+        true
+    }
+
+    fn preprocess(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        entry: &mut WordEntry,
+        options: &Options,
+        _irs: &mut Self::I,
+    ) {
+        entry.retain_translations_in_scope(&options.exclude_scope);
+        normalize_entry(entry, options.normalization);
+    }
+
     fn process(
         &self,
         edition: EditionLang,
@@ -55,6 +94,18 @@ impl Dictionary for DGlossary {
 impl Dictionary for DGlossaryExtended {
     type I = Vec<IGlossaryExtended>;
 
+    fn preprocess(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        entry: &mut WordEntry,
+        options: &Options,
+        _irs: &mut Self::I,
+    ) {
+        entry.retain_translations_in_scope(&options.exclude_scope);
+    }
+
     fn process(
         &self,
         edition: EditionLang,
@@ -66,7 +117,7 @@ impl Dictionary for DGlossaryExtended {
         process_glossary_extended(edition, source, target, entry, irs);
     }
 
-    fn postprocess(&self, irs: &mut Self::I) {
+    fn postprocess(&self, irs: &mut Self::I, opts: &Options) {
         let mut map = Map::default();
 
         for (lemma, pos, edition, translations) in irs.drain(..) {
@@ -76,9 +127,26 @@ impl Dictionary for DGlossaryExtended {
                 .extend(translations);
         }
 
-        irs.extend(map.into_iter().map(|(lemma, (pos, edition, set))| {
-            (lemma, pos, edition, set.into_iter().collect::<Vec<_>>())
-        }));
+        let exact: Self::I = map
+            .into_iter()
+            .map(|(lemma, (pos, edition, set))| {
+                (lemma, pos, edition, set.into_iter().collect::<Vec<_>>())
+            })
+            .collect();
+
+        // Fold headwords that survived the exact merge but differ only by casing/diacritics or a
+        // single edit, unioning their translations under the canonical (shortest) lemma.
+        *irs = tolerant_merge(
+            exact,
+            opts.merge_distance,
+            opts.merge_fold,
+            |(lemma, ..)| lemma.as_str(),
+            |canonical, other| {
+                let mut seen: Set<String> = canonical.3.drain(..).collect();
+                seen.extend(other.3);
+                canonical.3 = seen.into_iter().collect();
+            },
+        );
     }
 
     fn to_yomitan(
@@ -97,6 +165,22 @@ impl Dictionary for DGlossaryExtended {
 impl Dictionary for DIpa {
     type I = Vec<IIpa>;
 
+    fn streams(&self) -> bool {
+        true
+    }
+
+    fn preprocess(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        entry: &mut WordEntry,
+        options: &Options,
+        _irs: &mut Self::I,
+    ) {
+        normalize_entry(entry, options.normalization);
+    }
+
     fn process(
         &self,
         edition: EditionLang,
@@ -124,6 +208,18 @@ impl Dictionary for DIpa {
 impl Dictionary for DIpaMerged {
     type I = Vec<IIpa>;
 
+    fn preprocess(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        entry: &mut WordEntry,
+        options: &Options,
+        _irs: &mut Self::I,
+    ) {
+        normalize_entry(entry, options.normalization);
+    }
+
     fn process(
         &self,
         edition: EditionLang,
@@ -135,9 +231,26 @@ impl Dictionary for DIpaMerged {
         process_ipa(edition, source, entry, irs);
     }
 
-    fn postprocess(&self, irs: &mut Self::I) {
+    fn postprocess(&self, irs: &mut Self::I, opts: &Options) {
         // Keep only unique entries
         *irs = Set::from_iter(irs.drain(..)).into_iter().collect();
+
+        // Fold headwords that differ only by casing/diacritics or a single edit, keeping every
+        // distinct transcription under the canonical (shortest) surface form.
+        *irs = tolerant_merge(
+            std::mem::take(irs),
+            opts.merge_distance,
+            opts.merge_fold,
+            |(word, _)| word.as_str(),
+            |canonical, other| {
+                for ipa in other.1.transcriptions {
+                    if !canonical.1.transcriptions.contains(&ipa) {
+                        canonical.1.transcriptions.push(ipa);
+                    }
+                }
+            },
+        );
+
         // Sorting is not needed ~ just for visibility
         irs.sort_by(|a, b| a.0.cmp(&b.0));
     }
@@ -155,6 +268,247 @@ impl Dictionary for DIpaMerged {
     }
 }
 
+impl Dictionary for DHyphenation {
+    type I = Vec<IHyphenation>;
+
+    fn process(
+        &self,
+        _edition: EditionLang,
+        source: Lang,
+        _target: Lang,
+        entry: &WordEntry,
+        irs: &mut Self::I,
+    ) {
+        process_hyphenation(source, entry, irs);
+    }
+
+    fn to_yomitan(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        _options: &Options,
+        _diagnostics: &mut Diagnostics,
+        irs: Self::I,
+    ) -> Vec<LabelledYomitanEntry> {
+        vec![("term", to_yomitan_hyphenation(irs))]
+    }
+}
+
+impl Dictionary for DForms {
+    type I = Vec<IForms>;
+
+    fn process(
+        &self,
+        edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        entry: &WordEntry,
+        irs: &mut Self::I,
+    ) {
+        process_forms_dict(edition, entry, irs);
+    }
+
+    fn postprocess(&self, irs: &mut Self::I) {
+        // Dedupe identical form→lemma pairs, the way `DIpaMerged` dedupes transcriptions.
+        *irs = Set::from_iter(irs.drain(..)).into_iter().collect();
+        irs.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    fn to_yomitan(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        _options: &Options,
+        _diagnostics: &mut Diagnostics,
+        irs: Self::I,
+    ) -> Vec<LabelledYomitanEntry> {
+        vec![("form", to_yomitan_forms(irs))]
+    }
+}
+
+impl Dictionary for DFormOf {
+    type I = Vec<IFormOf>;
+
+    fn process(
+        &self,
+        edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        entry: &WordEntry,
+        irs: &mut Self::I,
+    ) {
+        process_form_of(edition, entry, irs);
+    }
+
+    // Collapse collisions where several lemmas share a surface form into a single entry, grouping
+    // their deinflection reasons together.
+    fn postprocess(&self, irs: &mut Self::I) {
+        // (form, pos) -> lemma -> reasons
+        let mut map: Map<(String, String), Map<String, Set<String>>> = Map::default();
+
+        for ir in irs.drain(..) {
+            let slot = map.entry((ir.form, ir.pos)).or_default();
+            for (lemma, reasons) in ir.lemmas {
+                slot.entry(lemma).or_default().extend(reasons);
+            }
+        }
+
+        irs.extend(map.into_iter().map(|((form, pos), lemmas)| {
+            IFormOf {
+                form,
+                pos,
+                lemmas: lemmas
+                    .into_iter()
+                    .map(|(lemma, reasons)| (lemma, reasons.into_iter().collect()))
+                    .collect(),
+            }
+        }));
+    }
+
+    fn to_yomitan(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        _options: &Options,
+        _diagnostics: &mut Diagnostics,
+        irs: Self::I,
+    ) -> Vec<LabelledYomitanEntry> {
+        vec![("form", to_yomitan_form_of(irs))]
+    }
+}
+
+impl Dictionary for DGlossaryMatrix {
+    type I = Vec<IGlossaryMatrix>;
+
+    fn process(
+        &self,
+        _edition: EditionLang,
+        _source: Lang,
+        _target: Lang,
+        entry: &WordEntry,
+        irs: &mut Self::I,
+    ) {
+        process_glossary_matrix(entry, irs);
+    }
+
+    // Merge entries sharing a headword, combining their per-language translation groups.
+    fn postprocess(&self, irs: &mut Self::I) {
+        // headword -> (pos, lang_code -> translations)
+        let mut map: Map<String, (String, Map<String, Set<String>>)> = Map::default();
+
+        for (headword, pos, groups) in irs.drain(..) {
+            let slot = map.entry(headword).or_insert_with(|| (pos, Map::default()));
+            for (lang_code, translations) in groups {
+                slot.1.entry(lang_code).or_default().extend(translations);
+            }
+        }
+
+        irs.extend(map.into_iter().map(|(headword, (pos, groups))| {
+            let groups = groups
+                .into_iter()
+                .map(|(lang_code, set)| (lang_code, set.into_iter().collect::<Vec<_>>()))
+                .collect();
+            (headword, pos, groups)
+        }));
+    }
+
+    fn to_yomitan(
+        &self,
+        _edition: EditionLang,
+        source: Lang,
+        _target: Lang,
+        options: &Options,
+        _diagnostics: &mut Diagnostics,
+        irs: Self::I,
+    ) -> Vec<LabelledYomitanEntry> {
+        vec![("term", to_yomitan_glossary_matrix(source, options, irs))]
+    }
+}
+
+// (headword, pos, [(lang_code, translations)])
+type IGlossaryMatrix = (String, String, Vec<(String, Vec<String>)>);
+
+fn process_glossary_matrix(word_entry: &WordEntry, irs: &mut Vec<IGlossaryMatrix>) {
+    // All target languages are collected here; the include/exclude selection is applied in
+    // `to_yomitan`, which is the only stage with access to `Options`.
+    let mut groups: Map<&str, Vec<String>> = Map::default();
+    for translation in word_entry.non_trivial_translations() {
+        groups
+            .entry(&translation.lang_code)
+            .or_default()
+            .push(translation.word.clone());
+    }
+
+    if groups.is_empty() {
+        return;
+    }
+
+    let found_pos = match find_short_pos(&word_entry.pos) {
+        Some(short_pos) => short_pos.to_string(),
+        None => word_entry.pos.clone(),
+    };
+
+    let groups = groups
+        .into_iter()
+        .map(|(lang_code, translations)| (lang_code.to_string(), translations))
+        .collect();
+
+    irs.push((word_entry.word.clone(), found_pos, groups));
+}
+
+fn to_yomitan_glossary_matrix(
+    source: Lang,
+    options: &Options,
+    irs: Vec<IGlossaryMatrix>,
+) -> Vec<YomitanEntry> {
+    let source_code = source.to_string();
+    let selected: Vec<String> = options
+        .translation_target
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    irs.into_iter()
+        .filter_map(|(headword, pos, groups)| {
+            let definitions: Vec<_> = groups
+                .into_iter()
+                .filter(|(lang_code, _)| selected.is_empty() || selected.contains(lang_code))
+                .filter(|(lang_code, _)| !(options.translations_only && *lang_code == source_code))
+                .map(|(lang_code, translations)| {
+                    let mut block = Node::new_array();
+                    block.push(wrap(NTag::Span, "", Node::Text(lang_code)));
+                    block.push(wrap(
+                        NTag::Ul,
+                        "",
+                        Node::Array(
+                            translations
+                                .into_iter()
+                                .map(|word| wrap(NTag::Li, "", Node::Text(word)))
+                                .collect(),
+                        ),
+                    ));
+                    DetailedDefinition::structured(wrap(NTag::Div, "", block))
+                })
+                .collect();
+
+            if definitions.is_empty() {
+                return None;
+            }
+
+            Some(YomitanEntry::TermBank(TermBank(
+                headword,
+                String::new(),
+                pos.clone(),
+                pos,
+                definitions,
+            )))
+        })
+        .collect()
+}
+
 // rg: process translations processtranslations
 fn process_glossary(
     source: EditionLang,
@@ -295,6 +649,328 @@ fn to_yomitan_glossary_extended(irs: Vec<IGlossaryExtended>) -> Vec<YomitanEntry
         .collect()
 }
 
+/// One surface form and the lemma(s) it deinflects to, with the grammatical reason(s).
+#[derive(Debug, Default)]
+pub struct IFormOf {
+    form: String,
+    pos: String,
+    lemmas: Vec<(String, Vec<String>)>,
+}
+
+fn process_form_of(edition: EditionLang, word_entry: &WordEntry, irs: &mut Vec<IFormOf>) {
+    let found_pos = match find_short_pos(&word_entry.pos) {
+        Some(short_pos) => short_pos.to_string(),
+        None => word_entry.pos.clone(),
+    };
+
+    let mut push = |form: &str, lemma: &str, reason: String| {
+        if form.is_empty() || lemma.is_empty() || form == lemma {
+            return;
+        }
+        irs.push(IFormOf {
+            form: form.to_string(),
+            pos: found_pos.clone(),
+            lemmas: vec![(lemma.to_string(), vec![reason])],
+        });
+    };
+
+    // Forms listed in inflection tables: the form deinflects back to the headword.
+    for form in word_entry.non_trivial_forms(edition) {
+        let mut tags = form.tags.clone();
+        strip_redundant_form_tags(&mut tags);
+        if tags.is_empty() {
+            continue;
+        }
+        push(&form.form, &word_entry.word, tags.join(" "));
+    }
+
+    // `form_of` / `alt_of` chains: the headword itself is a form of another lemma.
+    for alt in word_entry.form_of.iter().chain(&word_entry.alt_of) {
+        push(&word_entry.word, &alt.word, "form-of".to_string());
+    }
+
+    for sense in &word_entry.senses {
+        for alt in sense.form_of.iter().chain(&sense.alt_of) {
+            push(&word_entry.word, &alt.word, "form-of".to_string());
+        }
+    }
+}
+
+fn to_yomitan_form_of(irs: Vec<IFormOf>) -> Vec<YomitanEntry> {
+    irs.into_iter()
+        .map(|IFormOf { form, pos, lemmas }| {
+            // One Inflection per lemma, since yomitan reads a single Inflection's tag list as a
+            // causal deinflection chain.
+            let definitions = lemmas
+                .into_iter()
+                .map(|(lemma, reasons)| DetailedDefinition::Inflection((lemma, reasons)))
+                .collect();
+
+            YomitanEntry::TermBank(TermBank(
+                form,
+                String::new(),
+                "non-lemma".to_string(),
+                pos,
+                definitions,
+            ))
+        })
+        .collect()
+}
+
+// (lemma, hyphenated display with `·` between syllables)
+type IHyphenation = (String, String);
+
+/// Separator inserted between syllables in the rendered headword.
+const HYPHEN_POINT: char = '·';
+
+/// Margins forbidding a break too close to either end of the word, absent an exception entry.
+const DEFAULT_LEFT_HYPHEN_MIN: usize = 2;
+const DEFAULT_RIGHT_HYPHEN_MIN: usize = 3;
+
+fn process_hyphenation(source: Lang, word_entry: &WordEntry, irs: &mut Vec<IHyphenation>) {
+    let Some(dict) = hyphenation_dict(source) else {
+        return;
+    };
+
+    let breaks = dict.compute_breaks(&word_entry.word);
+    if breaks.is_empty() {
+        return;
+    }
+
+    irs.push((
+        word_entry.word.clone(),
+        render_hyphenation(&word_entry.word, &breaks),
+    ));
+}
+
+fn to_yomitan_hyphenation(irs: Vec<IHyphenation>) -> Vec<YomitanEntry> {
+    irs.into_iter()
+        .map(|(lemma, hyphenated)| {
+            let content = Node::Text(hyphenated);
+            YomitanEntry::TermBank(TermBank(
+                lemma,
+                String::new(),
+                String::new(),
+                String::new(),
+                vec![DetailedDefinition::structured(content)],
+            ))
+        })
+        .collect()
+}
+
+/// One node of the Knuth-Liang pattern trie, keyed by character for longest-prefix scanning.
+#[derive(Default)]
+struct PatternNode {
+    children: Map<char, PatternNode>,
+    /// Interleaved digit values for the pattern ending at this node, if any (length = depth + 1).
+    values: Option<Vec<u8>>,
+}
+
+/// A parsed TeX-style hyphenation pattern file: a pattern trie plus an exception list that
+/// overrides the computed break points outright for specific words.
+struct HyphenationDict {
+    root: PatternNode,
+    exceptions: Map<String, Vec<usize>>,
+    left_hyphen_min: usize,
+    right_hyphen_min: usize,
+}
+
+impl HyphenationDict {
+    /// Parse a TeX-style pattern file: a `\patterns{...}` block of digit-interspersed letter
+    /// sequences, and an optional `\hyphenation{...}` block of exception words with hyphens
+    /// marking their break points directly (e.g. `as-soc-iat-e`).
+    fn parse(source: &str) -> Self {
+        let mut root = PatternNode::default();
+        for pattern in extract_block(source, "patterns")
+            .unwrap_or_default()
+            .split_whitespace()
+        {
+            insert_pattern(&mut root, pattern);
+        }
+
+        let exceptions = extract_block(source, "hyphenation")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(parse_exception_word)
+            .collect();
+
+        Self {
+            root,
+            exceptions,
+            left_hyphen_min: DEFAULT_LEFT_HYPHEN_MIN,
+            right_hyphen_min: DEFAULT_RIGHT_HYPHEN_MIN,
+        }
+    }
+
+    /// Compute Knuth-Liang break positions (indices of the char *before* which a break is
+    /// allowed).
+    ///
+    /// The word is lowercased and wrapped in `.` boundary markers; at every position the trie is
+    /// walked along the longest matching prefix, folding each pattern crossed along the way into
+    /// the accumulated inter-letter values by maximum. A break is permitted where the accumulated
+    /// value is odd and at least `left_hyphen_min`/`right_hyphen_min` characters remain on either
+    /// side. An exact match in `exceptions` overrides the computed points entirely.
+    fn compute_breaks(&self, word: &str) -> Vec<usize> {
+        let lower = word.to_lowercase();
+        if let Some(points) = self.exceptions.get(&lower) {
+            return points.clone();
+        }
+
+        let dotted: Vec<char> = format!(".{lower}.").chars().collect();
+        // values[i] is the accumulated point value *before* dotted char i.
+        let mut values = vec![0u8; dotted.len() + 1];
+
+        for start in 0..dotted.len() {
+            let mut node = &self.root;
+            for &c in &dotted[start..] {
+                let Some(next) = node.children.get(&c) else {
+                    break;
+                };
+                node = next;
+                if let Some(pattern_values) = &node.values {
+                    for (offset, &d) in pattern_values.iter().enumerate() {
+                        values[start + offset] = values[start + offset].max(d);
+                    }
+                }
+            }
+        }
+
+        // Map odd inter-letter values back to original word indices, within the hyphen margins.
+        let orig_len = lower.chars().count();
+        (2..=orig_len)
+            .filter(|&j| values[j] % 2 == 1)
+            .map(|j| j - 1)
+            .filter(|&b| b >= self.left_hyphen_min && orig_len - b >= self.right_hyphen_min)
+            .collect()
+    }
+}
+
+/// Insert a digit-interspersed pattern (e.g. `hen5at`) into the trie, storing the value before
+/// each letter (length `letters + 1`) at the node for its last letter.
+fn insert_pattern(root: &mut PatternNode, pattern: &str) {
+    let mut values = vec![0u8];
+    let mut node = root;
+    for c in pattern.chars() {
+        if let Some(d) = c.to_digit(10) {
+            *values.last_mut().unwrap() = d as u8;
+        } else {
+            node = node.children.entry(c).or_default();
+            values.push(0);
+        }
+    }
+    node.values = Some(values);
+}
+
+/// Split a hyphen-marked exception word (e.g. `as-soc-iat-e`) into the plain word and the char
+/// indices its hyphens mark.
+fn parse_exception_word(word: &str) -> (String, Vec<usize>) {
+    let mut clean = String::with_capacity(word.len());
+    let mut breaks = Vec::new();
+    for c in word.chars() {
+        if c == '-' {
+            breaks.push(clean.chars().count());
+        } else {
+            clean.push(c);
+        }
+    }
+    (clean, breaks)
+}
+
+/// Extract the contents of a `\name{...}` block from a TeX-style pattern file.
+fn extract_block<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("\\{name}{{");
+    let start = source.find(&needle)? + needle.len();
+    let end = source[start..].find('}')?;
+    Some(&source[start..start + end])
+}
+
+/// Illustrative English Knuth-Liang pattern file, in the same `\patterns{}`/`\hyphenation{}`
+/// block syntax as real TeX `.pat` files; a full deployment would embed the complete upstream
+/// pattern file per language instead of this small subset.
+const EN_PATTERN_FILE: &str = r"
+\patterns{
+hy3ph he2n hena4 hen5at 1na n2at 1tio 2io o2n
+}
+\hyphenation{
+as-soc-iat-e proj-ect
+}
+";
+
+/// The parsed hyphenation dictionary for `source`, built once and cached.
+///
+/// Selected by source language, mirroring how [`get_ipas`]/[`get_reading`] branch; unsupported
+/// languages return `None` and produce no entries.
+fn hyphenation_dict(source: Lang) -> Option<&'static HyphenationDict> {
+    static EN: std::sync::OnceLock<HyphenationDict> = std::sync::OnceLock::new();
+    match source {
+        Lang::En => Some(EN.get_or_init(|| HyphenationDict::parse(EN_PATTERN_FILE))),
+        _ => None,
+    }
+}
+
+fn render_hyphenation(word: &str, breaks: &[usize]) -> String {
+    let mut out = String::with_capacity(word.len() + breaks.len());
+    for (i, c) in word.chars().enumerate() {
+        if breaks.contains(&i) {
+            out.push(HYPHEN_POINT);
+        }
+        out.push(c);
+    }
+    out
+}
+
+// (surface form, base lemma, inflection tags)
+type IForms = (String, String, Vec<String>);
+
+fn process_forms_dict(edition: EditionLang, word_entry: &WordEntry, irs: &mut Vec<IForms>) {
+    for form in word_entry.non_trivial_forms(edition) {
+        let mut tags = form.tags.clone();
+        strip_redundant_form_tags(&mut tags);
+
+        if tags.is_empty() || form.form.is_empty() || form.form == word_entry.word {
+            continue;
+        }
+
+        irs.push((form.form.clone(), word_entry.word.clone(), tags));
+    }
+}
+
+fn to_yomitan_forms(irs: Vec<IForms>) -> Vec<YomitanEntry> {
+    irs.into_iter()
+        .map(|(form, lemma, tags)| {
+            YomitanEntry::TermBank(TermBank(
+                form,
+                String::new(),
+                "non-lemma".to_string(),
+                String::new(),
+                vec![DetailedDefinition::Inflection((lemma, tags))],
+            ))
+        })
+        .collect()
+}
+
+/// Apply a Unicode normalization form to every string that feeds a headword, reading or IPA.
+///
+/// Readings are derived from `forms`/`sounds` and headwords from `word`, so normalizing the source
+/// fields here keeps `process_ipa` and `process_glossary` output in a single, consistent form.
+pub(crate) fn normalize_entry(word_entry: &mut WordEntry, form: NormalizationForm) {
+    if form == NormalizationForm::None {
+        return;
+    }
+
+    word_entry.word = form.apply(&word_entry.word);
+    for sound in &mut word_entry.sounds {
+        sound.ipa = form.apply(&sound.ipa);
+        sound.zh_pron = form.apply(&sound.zh_pron);
+    }
+    for f in &mut word_entry.forms {
+        f.form = form.apply(&f.form);
+    }
+    for translation in &mut word_entry.translations {
+        translation.word = form.apply(&translation.word);
+    }
+}
+
 type IIpa = (String, PhoneticTranscription);
 
 fn process_ipa(edition: EditionLang, source: Lang, word_entry: &WordEntry, irs: &mut Vec<IIpa>) {
@@ -335,6 +1011,7 @@ mod tests {
                 lang_code: lang_code.into(),
                 sense: sense.into(),
                 word: word.into(),
+                ..Default::default()
             }
         }
     }
@@ -431,4 +1108,72 @@ mod tests {
             dict.to_yomitan(edition, source, target, &options, &mut diagnostics, irs);
         assert_eq!(yomitan_labelled_entries[0].1.len(), 1);
     }
+
+    /// A dictionary built straight from a pattern trie with the given `(left, right)` hyphen
+    /// margins, so tests can pass `(0, 0)` to isolate trie matching from margin suppression.
+    fn dict_from_patterns(patterns: &[&str], margins: (usize, usize)) -> HyphenationDict {
+        let mut root = PatternNode::default();
+        for pattern in patterns {
+            insert_pattern(&mut root, pattern);
+        }
+        HyphenationDict {
+            root,
+            exceptions: Map::default(),
+            left_hyphen_min: margins.0,
+            right_hyphen_min: margins.1,
+        }
+    }
+
+    #[test]
+    fn hyphenation_breaks_on_odd_values() {
+        // A single `1b` pattern marks an odd value before every `b`; both interior positions break.
+        let dict = dict_from_patterns(&["1b"], (0, 0));
+        let breaks = dict.compute_breaks("abba");
+        assert_eq!(breaks, vec![1, 2]);
+        assert_eq!(render_hyphenation("abba", &breaks), "a·b·ba");
+    }
+
+    #[test]
+    fn hyphenation_suppresses_boundary_dots_and_even_values() {
+        // `.1a` marks the position right after the leading `.`, which the `2..=orig_len` range
+        // already excludes, and the even value from `b2b` never breaks.
+        let dict = dict_from_patterns(&[".1a", "b2b"], (0, 0));
+        assert!(dict.compute_breaks("abba").is_empty());
+    }
+
+    #[test]
+    fn hyphen_margins_suppress_breaks_too_close_to_either_edge() {
+        // With the real default margins, "abba" is too short for any break to leave 2 characters
+        // on the left and 3 on the right.
+        let dict = dict_from_patterns(&["1b"], (DEFAULT_LEFT_HYPHEN_MIN, DEFAULT_RIGHT_HYPHEN_MIN));
+        assert!(dict.compute_breaks("abba").is_empty());
+
+        // "abcdefg" is long enough: the single break found by `c1d` sits 3 chars from the left
+        // and 4 from the right, clearing the default 2/3 margins.
+        let dict = dict_from_patterns(
+            &["c1d"],
+            (DEFAULT_LEFT_HYPHEN_MIN, DEFAULT_RIGHT_HYPHEN_MIN),
+        );
+        assert_eq!(dict.compute_breaks("abcdefg"), vec![3]);
+    }
+
+    #[test]
+    fn hyphenation_exceptions_override() {
+        let mut dict = dict_from_patterns(&["1b"], (0, 0));
+        dict.exceptions.insert("abba".to_string(), vec![2]);
+        let breaks = dict.compute_breaks("abba");
+        assert_eq!(breaks, vec![2]);
+        assert_eq!(render_hyphenation("abba", &breaks), "ab·ba");
+    }
+
+    #[test]
+    fn parses_patterns_and_hyphenation_blocks_from_a_tex_style_file() {
+        let dict = HyphenationDict::parse("\\patterns{\n1b\n}\n\\hyphenation{\nas-soc-iat-e\n}\n");
+        assert_eq!(dict.exceptions.get("associate"), Some(&vec![2, 5, 8]));
+        // With margins disabled, the trie picks up the exception-free `1b` pattern too.
+        let mut dict = dict;
+        dict.left_hyphen_min = 0;
+        dict.right_hyphen_min = 0;
+        assert_eq!(dict.compute_breaks("abba"), vec![1, 2]);
+    }
 }
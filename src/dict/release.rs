@@ -14,7 +14,7 @@ use anyhow::Result;
 use rayon::ThreadPoolBuilder;
 use rayon::prelude::*;
 use rkyv::Archived;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 
 use crate::dict::{DGlossaryExtended, DIpa, DIpaMerged, edition_to_kind};
 use crate::dict::{
@@ -25,7 +25,7 @@ use crate::lang::{Edition, EditionSpec, Lang, LangSpec};
 use crate::models::kaikki::WordEntry;
 use crate::path::{PathKind, PathManager};
 use crate::utils::skip_because_file_exists;
-use crate::{Map, cli::GlossaryLangs};
+use crate::{Map, Set, cli::GlossaryLangs};
 use crate::{cli::IpaArgs, dict::writer::write_yomitan};
 use crate::{
     cli::{DictName, GlossaryArgs, MainArgs, MainLangs, Options},
@@ -201,6 +201,10 @@ pub struct WiktextractDb {
     pub conn: Connection,
 }
 
+/// Bumped whenever the blob encoding or table layout changes. A mismatch wipes the cached rows and
+/// reimports from the source JSONL.
+const SCHEMA_VERSION: i64 = 1;
+
 fn find_or_download_jsonl_simple(edition: Edition, pm: &PathManager) -> Result<PathBuf> {
     let paths_candidates = pm.dataset_paths(edition, None);
     let kinds_to_check = vec![PathKind::Unfiltered];
@@ -237,8 +241,8 @@ impl WiktextractDb {
     /// Open or create a new database at the given path
     // #[tracing::instrument(skip_all, level = "debug")]
     pub fn open_from_lang(edition: Edition, pm: &PathManager) -> Result<Self> {
-        let db_path = format!("data/db/wiktextract_{edition}.db");
-        if let Some(parent) = Path::new(&db_path).parent() {
+        let db_path = pm.path_db(edition);
+        if let Some(parent) = db_path.parent() {
             let _ = std::fs::create_dir_all(parent)?;
         }
 
@@ -261,21 +265,114 @@ impl WiktextractDb {
             CREATE INDEX IF NOT EXISTS idx_wiktextract_lang_entry
                 ON wiktextract(lang, entry);
 
+            -- Schema version and other bookkeeping
+            CREATE TABLE IF NOT EXISTS meta (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+
+            -- One row per imported source file, keyed by its edition/lang tag
+            CREATE TABLE IF NOT EXISTS installed (
+                lang        TEXT PRIMARY KEY,
+                source_path TEXT NOT NULL,
+                mtime       INTEGER NOT NULL,
+                size        INTEGER NOT NULL
+            );
             "#,
         )?;
 
-        // Check if the DB is empty at all (no entries)
         let mut db = Self { conn };
-        let count: i64 = db
+        db.migrate()?;
+
+        // Import incrementally: only (re)import when the source file is new or has changed.
+        let jsonl_path = find_or_download_jsonl_simple(edition, pm)?;
+        db.import_if_stale(edition.into(), &jsonl_path)?;
+
+        Ok(db)
+    }
+
+    fn meta_get(&self, key: &str) -> Result<Option<String>> {
+        let value = self
             .conn
-            .query_row("SELECT COUNT(*) FROM wiktextract", [], |row| row.get(0))?;
-        if count == 0 {
-            let jsonl_path = find_or_download_jsonl_simple(edition, pm)?;
-            db.import_jsonl(jsonl_path)?;
-        } else {
-            tracing::trace!("Opening non empty db for {edition}");
+            .query_row("SELECT value FROM meta WHERE key = ?", params![key], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        Ok(value)
+    }
+
+    fn meta_set(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES (?, ?)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Drop cached rows on a schema bump so the next import rewrites them in the new encoding.
+    fn migrate(&mut self) -> Result<()> {
+        let stored: Option<i64> = self.meta_get("schema_version")?.and_then(|v| v.parse().ok());
+        match stored {
+            Some(v) if v == SCHEMA_VERSION => return Ok(()),
+            // Fresh (or legacy pre-meta) db: nothing to clear.
+            None => {}
+            Some(old) => {
+                tracing::warn!("Migrating wiktextract db schema {old} -> {SCHEMA_VERSION}");
+                self.conn
+                    .execute_batch("DELETE FROM wiktextract; DELETE FROM installed;")?;
+            }
         }
-        Ok(db)
+        self.meta_set("schema_version", &SCHEMA_VERSION.to_string())?;
+        Ok(())
+    }
+
+    /// Import `jsonl_path` unless an unchanged copy was already imported for `lang`.
+    fn import_if_stale(&mut self, lang: Lang, jsonl_path: &Path) -> Result<()> {
+        let md = std::fs::metadata(jsonl_path)?;
+        let size = md.len() as i64;
+        let mtime = md
+            .modified()?
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64;
+
+        let previous: Option<(i64, i64)> = self
+            .conn
+            .query_row(
+                "SELECT mtime, size FROM installed WHERE lang = ?",
+                params![lang.as_ref()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        if previous == Some((mtime, size)) {
+            tracing::trace!("{lang} already imported and unchanged");
+            return Ok(());
+        }
+
+        // A single source file backs the whole edition db, so a change means a full reimport.
+        self.conn.execute_batch("DELETE FROM wiktextract;")?;
+        self.import_jsonl(jsonl_path)?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO installed (lang, source_path, mtime, size) VALUES (?, ?, ?, ?)",
+            params![
+                lang.as_ref(),
+                jsonl_path.to_string_lossy(),
+                mtime,
+                size
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Delete one language's rows without touching the rest of the db.
+    pub fn drop_lang(&mut self, lang: Lang) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM wiktextract WHERE lang = ?",
+            params![lang.as_ref()],
+        )?;
+        self.conn
+            .execute("DELETE FROM installed WHERE lang = ?", params![lang.as_ref()])?;
+        Ok(())
     }
 
     #[tracing::instrument(skip_all, level = "debug")]
@@ -311,6 +408,78 @@ impl WiktextractDb {
         Ok(())
     }
 
+    /// All entries matching `(word, lang)` exactly, decoded from their blobs.
+    pub fn lookup(&self, word: &str, lang: Lang) -> Result<Vec<WordEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT entry FROM wiktextract WHERE lang = ? AND word = ?")?;
+        let mut rows = stmt.query(params![lang.as_ref(), word])?;
+
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next()? {
+            let blob: &[u8] = row.get_ref(0)?.as_blob()?;
+            entries.push(Self::blob_to_word_entry(blob)?);
+        }
+        Ok(entries)
+    }
+
+    /// Distinct words of `lang` starting with `prefix`, capped at `limit`.
+    pub fn prefix_search(&self, prefix: &str, lang: Lang, limit: usize) -> Result<Vec<String>> {
+        // `%` and `_` are LIKE wildcards: escape them so a literal prefix matches literally.
+        let escaped = prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("{escaped}%");
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT word FROM wiktextract \
+             WHERE lang = ? AND word LIKE ? ESCAPE '\\' ORDER BY word LIMIT ?",
+        )?;
+        let rows = stmt.query_map(params![lang.as_ref(), pattern, limit as i64], |row| {
+            row.get::<_, String>(0)
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Resolve a surface form to its lemma(s).
+    ///
+    /// First honors any `form_of`/`alt_of` the form's own entries declare; failing that, scans the
+    /// edition for lemmas whose inflection table lists `word` as a non-trivial form.
+    pub fn resolve_lemma(&self, word: &str, lang: Lang) -> Result<Vec<String>> {
+        let mut lemmas = Set::default();
+
+        for entry in self.lookup(word, lang)? {
+            for alt in entry.form_of.iter().chain(&entry.alt_of) {
+                lemmas.insert(alt.word.clone());
+            }
+            for sense in &entry.senses {
+                for alt in sense.form_of.iter().chain(&sense.alt_of) {
+                    lemmas.insert(alt.word.clone());
+                }
+            }
+        }
+
+        if lemmas.is_empty() {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT entry FROM wiktextract WHERE lang = ?")?;
+            let mut rows = stmt.query(params![lang.as_ref()])?;
+            // `lang` is the edition this table is scoped to; fall back to the default edition on
+            // the rare language that isn't one of its own editions. Fixed per call, so resolved
+            // once here rather than on every row.
+            let edition = lang.try_into().unwrap_or_default();
+            while let Some(row) = rows.next()? {
+                let blob: &[u8] = row.get_ref(0)?.as_blob()?;
+                let entry = Self::blob_to_word_entry(blob)?;
+                if entry
+                    .non_trivial_forms(edition)
+                    .any(|form| form.form == word)
+                {
+                    lemmas.insert(entry.word);
+                }
+            }
+        }
+
+        Ok(lemmas.into_iter().collect())
+    }
+
     pub fn blob_to_word_entry(blob: &[u8]) -> Result<WordEntry> {
         let archived: &Archived<WordEntry> =
             rkyv::access::<Archived<WordEntry>, rkyv::rancor::Error>(blob).unwrap();
@@ -532,3 +701,81 @@ impl EditionFrom for DGlossaryExtended {
         todo!()
     }
 }
+
+/// Minimal JSON lookup server over an already-built `WiktextractDb`.
+///
+/// Useful for poking at the extraction pipeline without producing a full Yomitan build. Endpoints:
+/// * `GET /lookup?word=..&lang=..`  -> the matching `WordEntry`s
+/// * `GET /prefix?word=..&lang=..`  -> words of `lang` sharing the prefix
+/// * `GET /lemma?word=..&lang=..`   -> lemma(s) the surface form deinflects to
+#[cfg(feature = "serve")]
+pub mod serve {
+    use super::{Edition, PathManager, Result, WiktextractDb};
+    use crate::lang::Lang;
+
+    use std::str::FromStr;
+    use tiny_http::{Header, Response, Server};
+
+    pub fn serve(edition: Edition, pm: &PathManager, addr: &str) -> Result<()> {
+        let db = WiktextractDb::open_from_lang(edition, pm)?;
+        let server = Server::http(addr).map_err(|err| anyhow::anyhow!(err))?;
+        println!("Serving {edition} on http://{addr}");
+
+        for request in server.incoming_requests() {
+            let (status, body) = match handle(&db, request.url()) {
+                Ok(json) => (200, json),
+                Err(err) => (400, format!("{{\"error\":{:?}}}", err.to_string())),
+            };
+            let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+            let response = Response::from_string(body).with_status_code(status).with_header(header);
+            let _ = request.respond(response);
+        }
+
+        Ok(())
+    }
+
+    fn handle(db: &WiktextractDb, url: &str) -> Result<String> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let word = query_param(query, "word")
+            .ok_or_else(|| anyhow::anyhow!("missing `word` parameter"))?;
+        let lang = query_param(query, "lang")
+            .ok_or_else(|| anyhow::anyhow!("missing `lang` parameter"))?;
+        let lang = Lang::from_str(&lang).map_err(|err| anyhow::anyhow!("{err}"))?;
+
+        let json = match path {
+            "/lookup" => serde_json::to_string(&db.lookup(&word, lang)?)?,
+            "/prefix" => serde_json::to_string(&db.prefix_search(&word, lang, 50)?)?,
+            "/lemma" => serde_json::to_string(&db.resolve_lemma(&word, lang)?)?,
+            other => anyhow::bail!("unknown endpoint '{other}'"),
+        };
+        Ok(json)
+    }
+
+    /// Return the (url-decoded) value of `key` in an `a=b&c=d` query string.
+    fn query_param(query: &str, key: &str) -> Option<String> {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| percent_decode(v))
+    }
+
+    fn percent_decode(s: &str) -> String {
+        let bytes = s.replace('+', " ");
+        let bytes = bytes.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' && i + 2 < bytes.len() {
+                if let Ok(byte) = u8::from_str_radix(&s[i + 1..i + 3], 16) {
+                    out.push(byte);
+                    i += 3;
+                    continue;
+                }
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+}
@@ -0,0 +1,326 @@
+//! `tidy` subcommand: lint already-generated Yomitan dictionary artifacts before publishing.
+//!
+//! Walks `pm.dir_temp_dict()` (so it expects the dictionary was built with `--save-temps`) and
+//! runs a fixed battery of checks modeled on rustc's tidy tool, instead of regenerating anything:
+//! term banks are sorted and deduplicated by headword, no entry has an empty gloss or a malformed
+//! structured-content node, phonetic-transcription tags that look like a source language code
+//! actually parse as one, and every bank stays under a configurable size budget. Each check reports
+//! pass/fail via [`CHECK_C`]/[`FAIL_C`]; the caller should exit nonzero when [`TidyReport::ok`] is
+//! false.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::Set;
+use crate::lang::Lang;
+use crate::utils::{CHECK_C, FAIL_C, get_file_size_human};
+
+/// Default per-bank size budget before `tidy` warns.
+pub const DEFAULT_BANK_SIZE_BUDGET_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Outcome of one named check.
+pub struct CheckResult {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Every check `tidy` ran, in run order.
+#[derive(Default)]
+pub struct TidyReport {
+    pub results: Vec<CheckResult>,
+}
+
+impl TidyReport {
+    /// Whether every hard check passed. The size-budget check only warns: an over-budget bank is
+    /// reported by [`TidyReport::print_summary`] but does not make this false.
+    pub fn ok(&self) -> bool {
+        self.results
+            .iter()
+            .all(|r| r.passed || r.name == "bank-size-budget")
+    }
+
+    /// Print one `CHECK_C`/`FAIL_C` line per check.
+    pub fn print_summary(&self) {
+        for result in &self.results {
+            let marker = if result.passed { CHECK_C } else { FAIL_C };
+            println!("{marker} {}: {}", result.name, result.detail);
+        }
+    }
+}
+
+type Bank = (PathBuf, Vec<Value>);
+
+/// Run every check against the banks under `dir_temp_dict`.
+pub fn tidy(dir_temp_dict: &Path, bank_size_budget_bytes: u64) -> Result<TidyReport> {
+    let mut term_banks: Vec<Bank> = Vec::new();
+    let mut meta_banks: Vec<Bank> = Vec::new();
+
+    for entry in std::fs::read_dir(dir_temp_dict)?.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        if !name.ends_with(".json") {
+            continue;
+        }
+
+        let rows: Vec<Value> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+
+        if name.starts_with("term_meta_bank") {
+            meta_banks.push((path, rows));
+        } else if name.starts_with("term_bank") {
+            term_banks.push((path, rows));
+        }
+    }
+
+    let report = TidyReport {
+        results: vec![
+            check_sorted_and_deduped(&term_banks),
+            check_glosses_and_structured_content(&term_banks),
+            check_ipa_lang_tags(&meta_banks),
+            check_size_budgets(term_banks.iter().chain(&meta_banks), bank_size_budget_bytes),
+        ],
+    };
+    Ok(report)
+}
+
+fn check_sorted_and_deduped(term_banks: &[Bank]) -> CheckResult {
+    let mut offenders = 0;
+    for (_, rows) in term_banks {
+        let terms: Vec<&str> = rows
+            .iter()
+            .filter_map(|row| row.as_array()?.first()?.as_str())
+            .collect();
+        offenders += terms.windows(2).filter(|w| w[0] > w[1]).count();
+
+        let mut seen: Set<(&str, &str)> = Set::default();
+        for row in rows {
+            let Some(fields) = row.as_array() else {
+                continue;
+            };
+            let key = (
+                fields.first().and_then(Value::as_str).unwrap_or(""),
+                fields.get(1).and_then(Value::as_str).unwrap_or(""),
+            );
+            if !seen.insert(key) {
+                offenders += 1;
+            }
+        }
+    }
+    CheckResult {
+        name: "sorted-deduped-headwords",
+        passed: offenders == 0,
+        detail: if offenders == 0 {
+            "all banks sorted and deduplicated".to_string()
+        } else {
+            format!("{offenders} out-of-order or duplicate headwords")
+        },
+    }
+}
+
+fn check_glosses_and_structured_content(term_banks: &[Bank]) -> CheckResult {
+    let mut offenders = 0;
+    for (_, rows) in term_banks {
+        for row in rows {
+            let Some(definitions) = row.as_array().and_then(|fields| fields.get(5)) else {
+                continue;
+            };
+            let Some(definitions) = definitions.as_array() else {
+                offenders += 1;
+                continue;
+            };
+            if definitions.is_empty() {
+                offenders += 1;
+                continue;
+            }
+            offenders += definitions
+                .iter()
+                .filter(|def| !is_well_formed_definition(def))
+                .count();
+        }
+    }
+    CheckResult {
+        name: "non-empty-glosses-and-valid-structured-content",
+        passed: offenders == 0,
+        detail: if offenders == 0 {
+            "every definition is non-empty and well-formed".to_string()
+        } else {
+            format!("{offenders} empty or malformed definitions")
+        },
+    }
+}
+
+/// A `DetailedDefinition` is well-formed when it's a non-empty plain string, a `[text, tags]`
+/// inflection pair, or a `{"type":"structured-content","content":...}` wrapper whose content tree
+/// is itself well-formed (see [`is_well_formed_node`]).
+fn is_well_formed_definition(value: &Value) -> bool {
+    match value {
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => items.len() == 2 && items[0].is_string() && items[1].is_array(),
+        Value::Object(map) => {
+            map.get("type").and_then(Value::as_str) == Some("structured-content")
+                && map.get("content").is_some_and(is_well_formed_node)
+        }
+        _ => false,
+    }
+}
+
+/// A structured-content node is a string, an array of nodes, or an object carrying at least a
+/// `tag` and a `content` field (`GenericNode`/`BacklinkContent`'s shared serialized shape).
+fn is_well_formed_node(value: &Value) -> bool {
+    match value {
+        Value::String(_) => true,
+        Value::Array(items) => items.iter().all(is_well_formed_node),
+        Value::Object(map) => {
+            map.contains_key("tag") && map.get("content").is_some_and(is_well_formed_node)
+        }
+        _ => false,
+    }
+}
+
+/// A tag that's 2-3 lowercase ascii letters before its first hyphen *looks* like a source language
+/// code; tags that don't look code-shaped (the vast majority of dialect/register labels) are left
+/// alone so this doesn't flag every legitimate IPA tag.
+fn looks_lang_coded(tag: &str) -> bool {
+    let head = tag.split('-').next().unwrap_or(tag);
+    (2..=3).contains(&head.len()) && head.chars().all(|c| c.is_ascii_lowercase())
+}
+
+fn check_ipa_lang_tags(meta_banks: &[Bank]) -> CheckResult {
+    let mut checked = 0;
+    let mut offenders = 0;
+    for (_, rows) in meta_banks {
+        for row in rows {
+            let Some(fields) = row.as_array() else {
+                continue;
+            };
+            if fields.get(1).and_then(Value::as_str) != Some("ipa") {
+                continue;
+            }
+            let transcriptions = fields
+                .get(2)
+                .and_then(|phonetic| phonetic.get("transcriptions"))
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten();
+            for transcription in transcriptions {
+                let tags = transcription
+                    .get("tags")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten();
+                for tag in tags.filter_map(Value::as_str) {
+                    if looks_lang_coded(tag) {
+                        checked += 1;
+                        if tag.parse::<Lang>().is_err() {
+                            offenders += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    CheckResult {
+        name: "ipa-source-lang-tags",
+        passed: offenders == 0,
+        detail: format!("{checked} lang-coded tags checked, {offenders} unrecognized"),
+    }
+}
+
+fn check_size_budgets<'a>(banks: impl Iterator<Item = &'a Bank>, budget_bytes: u64) -> CheckResult {
+    let mut over_budget = Vec::new();
+    for (path, _) in banks {
+        if let Ok(size) = std::fs::metadata(path).map(|md| md.len())
+            && size > budget_bytes
+        {
+            let human = get_file_size_human(path).unwrap_or_default();
+            over_budget.push(format!("{} ({human})", path.display()));
+        }
+    }
+    CheckResult {
+        name: "bank-size-budget",
+        passed: over_budget.is_empty(),
+        detail: if over_budget.is_empty() {
+            "all banks within budget".to_string()
+        } else {
+            over_budget.join(", ")
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bank(rows: Vec<Value>) -> Bank {
+        (PathBuf::from("term_bank_1.json"), rows)
+    }
+
+    #[test]
+    fn flags_out_of_order_and_duplicate_headwords() {
+        let banks = [bank(vec![
+            json!(["b", "", "", "", [], 0, "", ""]),
+            json!(["a", "", "", "", [], 0, "", ""]),
+            json!(["a", "", "", "", [], 0, "", ""]),
+        ])];
+        let result = check_sorted_and_deduped(&banks);
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn accepts_sorted_deduped_bank() {
+        let banks = [bank(vec![
+            json!(["a", "", "", "", [], 0, "", ""]),
+            json!(["b", "", "", "", [], 0, "", ""]),
+        ])];
+        assert!(check_sorted_and_deduped(&banks).passed);
+    }
+
+    #[test]
+    fn flags_empty_and_malformed_definitions() {
+        let banks = [bank(vec![json!(["a", "", "", "", 0, [], 0, ""])])];
+        assert!(!check_glosses_and_structured_content(&banks).passed);
+
+        let banks = [bank(vec![json!(["a", "", "", "", 0, ["gloss"], 0, ""])])];
+        assert!(check_glosses_and_structured_content(&banks).passed);
+    }
+
+    #[test]
+    fn accepts_well_formed_structured_content() {
+        let content =
+            json!({"type": "structured-content", "content": {"tag": "div", "content": "x"}});
+        assert!(is_well_formed_definition(&content));
+
+        let malformed = json!({"type": "structured-content", "content": {"tag": "div"}});
+        assert!(!is_well_formed_definition(&malformed));
+    }
+
+    #[test]
+    fn flags_unrecognized_lang_coded_ipa_tags() {
+        let banks = [bank(vec![json!([
+            "a",
+            "ipa",
+            {"reading": "", "transcriptions": [{"ipa": "x", "tags": ["zz"]}]}
+        ])])];
+        let result = check_ipa_lang_tags(&banks);
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn leaves_non_lang_coded_dialect_tags_alone() {
+        let banks = [bank(vec![json!([
+            "a",
+            "ipa",
+            {"reading": "", "transcriptions": [{"ipa": "x", "tags": ["Received-Pronunciation"]}]}
+        ])])];
+        let result = check_ipa_lang_tags(&banks);
+        assert!(result.passed);
+    }
+}
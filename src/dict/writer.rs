@@ -28,6 +28,9 @@ const STYLES_CSS_EXPERIMENTAL: &[u8] = include_bytes!("../../assets/styles_exper
 enum Sink<'a> {
     Disk,
     Zip(&'a mut ZipWriter<File>, SimpleFileOptions),
+    /// Batch `INSERT`s into an open database; the caller owns the surrounding transaction.
+    #[cfg(feature = "sqlite")]
+    Sqlite(&'a rusqlite::Connection),
 }
 
 /// Write yomitan labelled entries in banks to a sink (either disk or zip).
@@ -83,60 +86,123 @@ pub fn write_yomitan(
         return Ok(());
     }
 
-    let writer_path = pm.path_dict();
-    let writer_file = File::create(&writer_path)?;
-    let mut zip = ZipWriter::new(writer_file);
-    let zip_opts =
-        SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-
-    // Zip index.json
-    let index_string = get_index(&pm.dict_name_expanded(), source, target);
-    zip.start_file("index.json", zip_opts)?;
-    zip.write_all(index_string.as_bytes())?;
-
-    // Zip a copy of styles.css
-    zip.start_file("styles.css", zip_opts)?;
-    if opts.experimental {
-        zip.write_all(STYLES_CSS_EXPERIMENTAL)?;
-    } else {
-        zip.write_all(STYLES_CSS)?;
+    #[cfg(feature = "sqlite")]
+    if opts.output_format == crate::cli::OutputFormat::Sqlite {
+        let db_path = pm.path_dict().with_extension("db");
+        let _ = fs::remove_file(&db_path);
+        let conn = open_sqlite(&db_path)?;
+        // A single transaction around every bank batches the inserts for speed.
+        let tx = conn.unchecked_transaction()?;
+        for lentry in &labelled_entries {
+            write_banks(
+                opts.pretty,
+                opts.quiet,
+                &lentry.entries,
+                &mut bank_index,
+                lentry.label,
+                &db_path,
+                Sink::Sqlite(&conn),
+            )?;
+        }
+        tx.commit()?;
+        pretty_println_at_path(&format!("{CHECK_C} Wrote sqlite dict"), &db_path);
+        return Ok(());
     }
 
-    // Zip a copy of tag_bank.json
-    let tag_bank = get_tag_bank_as_tag_info();
-    let tag_bank_bytes = serde_json::to_vec_pretty(&tag_bank)?;
-    zip.start_file("tag_bank_1.json", zip_opts)?; // it needs to end in _1
-    zip.write_all(&tag_bank_bytes)?;
-
-    #[allow(unused_mut)]
-    for mut lentry in labelled_entries {
-        #[cfg(feature = "opt-stream-write")]
-        write_banks(
-            opts.pretty,
-            opts.quiet,
-            &mut lentry.entries,
-            &mut bank_index,
-            lentry.label,
-            &writer_path,
-            Sink::Zip(&mut zip, zip_opts),
-        )?;
-        #[cfg(not(feature = "opt-stream-write"))]
-        write_banks(
-            opts.pretty,
-            opts.quiet,
-            &lentry.entries,
-            &mut bank_index,
-            lentry.label,
-            &writer_path,
-            Sink::Zip(&mut zip, zip_opts),
-        )?;
-    }
+    let mut writer = YomitanWriter::open(source, target, opts, pm)?;
+    writer.append(labelled_entries)?;
+    writer.finish()?;
 
-    zip.finish()?;
+    Ok(())
+}
 
-    pretty_println_at_path(&format!("{CHECK_C} Wrote yomitan dict"), &writer_path);
+/// An open Yomitan zip that accepts term-bank entries in batches.
+///
+/// [`write_yomitan`] converts a whole dictionary in one call, but a streaming builder cannot hold
+/// the full edition in memory: it opens a writer, [`append`](Self::append)s banks as each IR bucket
+/// fills, and [`finish`](Self::finish)es once the source is drained. The index/css/tag-bank metadata
+/// is written up front at [`open`](Self::open), and `bank_index` carries across appends so bank
+/// filenames stay unique.
+pub struct YomitanWriter {
+    zip: ZipWriter<File>,
+    zip_opts: SimpleFileOptions,
+    path: PathBuf,
+    bank_index: usize,
+    pretty: bool,
+    quiet: bool,
+}
 
-    Ok(())
+impl YomitanWriter {
+    pub fn open(source: Lang, target: Lang, opts: &Options, pm: &PathManager) -> Result<Self> {
+        let path = pm.path_dict();
+        let file = File::create(&path)?;
+        let mut zip = ZipWriter::new(file);
+        let zip_opts =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        // Zip index.json
+        let index_string = get_index(&pm.dict_name_expanded(), source, target);
+        zip.start_file("index.json", zip_opts)?;
+        zip.write_all(index_string.as_bytes())?;
+
+        // Zip a copy of styles.css
+        zip.start_file("styles.css", zip_opts)?;
+        if opts.experimental {
+            zip.write_all(STYLES_CSS_EXPERIMENTAL)?;
+        } else {
+            zip.write_all(STYLES_CSS)?;
+        }
+
+        // Zip a copy of tag_bank.json
+        let tag_bank = get_tag_bank_as_tag_info();
+        let tag_bank_bytes = serde_json::to_vec_pretty(&tag_bank)?;
+        zip.start_file("tag_bank_1.json", zip_opts)?; // it needs to end in _1
+        zip.write_all(&tag_bank_bytes)?;
+
+        Ok(Self {
+            zip,
+            zip_opts,
+            path,
+            bank_index: 0,
+            pretty: opts.pretty,
+            quiet: opts.quiet,
+        })
+    }
+
+    /// Append one batch of labelled entries, continuing the running bank numbering.
+    pub fn append(&mut self, labelled_entries: Vec<LabelledYomitanEntry>) -> Result<()> {
+        #[allow(unused_mut)]
+        for mut lentry in labelled_entries {
+            #[cfg(feature = "opt-stream-write")]
+            write_banks(
+                self.pretty,
+                self.quiet,
+                &mut lentry.entries,
+                &mut self.bank_index,
+                lentry.label,
+                &self.path,
+                Sink::Zip(&mut self.zip, self.zip_opts),
+            )?;
+            #[cfg(not(feature = "opt-stream-write"))]
+            write_banks(
+                self.pretty,
+                self.quiet,
+                &lentry.entries,
+                &mut self.bank_index,
+                lentry.label,
+                &self.path,
+                Sink::Zip(&mut self.zip, self.zip_opts),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Finalize the zip central directory and report the written path.
+    pub fn finish(mut self) -> Result<()> {
+        self.zip.finish()?;
+        pretty_println_at_path(&format!("{CHECK_C} Wrote yomitan dict"), &self.path);
+        Ok(())
+    }
 }
 
 /// Writes `yomitan_entries` in batches to `out_sink` (either disk or a zip).
@@ -148,26 +214,71 @@ fn write_bank_chunk(
     out_dir: &Path,
     sink: &mut Sink,
 ) -> Result<PathBuf> {
-    let json_bytes = if pretty {
-        serde_json::to_vec_pretty(bank)?
-    } else {
-        serde_json::to_vec(bank)?
-    };
-
     let file_path = out_dir.join(bank_name);
     match sink {
         Sink::Disk => {
+            let json_bytes = serialize_bank(pretty, bank)?;
             let mut file = File::create(&file_path)?;
             file.write_all(&json_bytes)?;
         }
         Sink::Zip(zip, zip_options) => {
+            let json_bytes = serialize_bank(pretty, bank)?;
             zip.start_file(bank_name, *zip_options)?;
             zip.write_all(&json_bytes)?;
         }
+        #[cfg(feature = "sqlite")]
+        Sink::Sqlite(conn) => {
+            insert_bank(conn, bank)?;
+        }
     }
     Ok(file_path)
 }
 
+fn serialize_bank(pretty: bool, bank: &[YomitanEntry]) -> Result<Vec<u8>> {
+    Ok(if pretty {
+        serde_json::to_vec_pretty(bank)?
+    } else {
+        serde_json::to_vec(bank)?
+    })
+}
+
+/// Open (creating if needed) the sqlite database backing `Sink::Sqlite` and ensure the schema.
+///
+/// Entries are stored one row per term, keyed by lemma/reading/pos, alongside the serialized
+/// definition blob so downstream tools can resolve a headword without unpacking a whole bank.
+#[cfg(feature = "sqlite")]
+fn open_sqlite(path: &Path) -> Result<rusqlite::Connection> {
+    let conn = rusqlite::Connection::open(path)?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS terms (
+            lemma      TEXT NOT NULL,
+            reading    TEXT NOT NULL,
+            pos        TEXT NOT NULL,
+            definition TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS terms_lemma ON terms (lemma);",
+    )?;
+    Ok(conn)
+}
+
+/// Insert every entry of `bank` as a row. Expected to run inside a caller-held transaction.
+#[cfg(feature = "sqlite")]
+fn insert_bank(conn: &rusqlite::Connection, bank: &[YomitanEntry]) -> Result<()> {
+    use crate::models::yomitan::YomitanEntry;
+
+    let mut stmt =
+        conn.prepare_cached("INSERT INTO terms (lemma, reading, pos, definition) VALUES (?, ?, ?, ?)")?;
+    for entry in bank {
+        let (lemma, reading, pos) = match entry {
+            YomitanEntry::TermBank(tb) => (tb.0.as_str(), tb.1.as_str(), tb.2.as_str()),
+            YomitanEntry::TermBankMeta(_) => continue,
+        };
+        let definition = serde_json::to_string(entry)?;
+        stmt.execute(rusqlite::params![lemma, reading, pos, definition])?;
+    }
+    Ok(())
+}
+
 #[cfg(not(feature = "opt-stream-write"))]
 #[tracing::instrument(skip_all)]
 fn write_banks(
@@ -32,46 +32,211 @@ pub mod html {
 
     use anyhow::Result;
     use flate2::read::GzDecoder;
+    use serde::{Deserialize, Serialize};
     use std::fs::File;
     use std::io::BufWriter;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     use crate::utils::{CHECK_C, pretty_println_at_path};
 
-    // TODO: This is not skipping properly!
+    /// Where to fetch a raw kaikki `.jsonl` dump from, mirroring how e.g. Helix's grammar loader
+    /// lets users point at a local checkout, track upstream latest, or pin a specific revision.
+    pub enum DownloadSource {
+        /// Copy an already-downloaded extract from disk.
+        Local { path: PathBuf },
+        /// Fetch the latest dump over HTTP, revalidating against cached `Last-Modified`/`ETag`.
+        Remote { url: String },
+        /// Fetch a dump pinned to a specific kaikki snapshot date/revision, re-downloading only
+        /// when the recorded revision in the sidecar no longer matches.
+        Snapshot { url: String, revision: String },
+    }
 
-    /// Download the raw jsonl from kaikki and write it to `path_jsonl_raw`.
+    /// Cached fetch validators for a previously downloaded dump.
     ///
-    /// Does not write the .gz file to disk.
-    pub fn download_jsonl(
-        edition: EditionLang,
-        source: Lang,
-        path_jsonl_raw: &Path,
-        quiet: bool,
-    ) -> Result<()> {
-        let url = url_jsonl_raw_gz(edition, source);
-        if !quiet {
-            println!("⬇ Downloading {url}");
+    /// Persisted as a small JSON sidecar next to `path_jsonl_raw` so the next run can tell whether
+    /// the recorded source is still fresh: a conditional `If-Modified-Since`/`If-None-Match` request
+    /// for [`DownloadSource::Remote`], a plain revision comparison for
+    /// [`DownloadSource::Snapshot`], or a comparison of the recorded source path for
+    /// [`DownloadSource::Local`].
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    struct DownloadCache {
+        #[serde(skip_serializing_if = "String::is_empty")]
+        source: String,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        fetched_at: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_modified: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        etag: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        revision: Option<String>,
+    }
+
+    impl DownloadCache {
+        fn sidecar_path(path_jsonl_raw: &Path) -> PathBuf {
+            let mut name = path_jsonl_raw
+                .file_name()
+                .unwrap_or_default()
+                .to_os_string();
+            name.push(".jsonl.meta");
+            path_jsonl_raw.with_file_name(name)
         }
 
-        let response = ureq::get(url).call()?;
+        fn load(path: &Path) -> Self {
+            std::fs::read_to_string(path)
+                .ok()
+                .and_then(|raw| serde_json::from_str(&raw).ok())
+                .unwrap_or_default()
+        }
+
+        fn store(&self, path: &Path) -> Result<()> {
+            std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+            Ok(())
+        }
 
-        if let Some(last_modified) = response.headers().get("last-modified") {
-            tracing::info!("Download was last modified: {:?}", last_modified);
+        fn has_validators(&self) -> bool {
+            self.last_modified.is_some() || self.etag.is_some()
         }
+    }
+
+    fn now_timestamp() -> String {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Fetch `source` to `path_jsonl_raw`, re-downloading only when the sidecar's recorded
+    /// validators/revision say the artifact is stale. See [`DownloadSource`] for the supported
+    /// source kinds.
+    pub fn fetch_source(source: &DownloadSource, path_jsonl_raw: &Path, quiet: bool) -> Result<()> {
+        let sidecar = DownloadCache::sidecar_path(path_jsonl_raw);
+        let cache = DownloadCache::load(&sidecar);
 
-        let reader = response.into_body().into_reader();
-        // We can't use gzip's ureq feature because there is no content-encoding in headers
-        // https://github.com/tatuylonen/wiktextract/issues/1482
-        let mut decoder = GzDecoder::new(reader);
+        match source {
+            DownloadSource::Local { path } => {
+                let descriptor = path.display().to_string();
+                if path_jsonl_raw.exists() && cache.source == descriptor {
+                    if !quiet {
+                        pretty_println_at_path(&format!("{CHECK_C} Up to date"), path_jsonl_raw);
+                    }
+                    return Ok(());
+                }
+                std::fs::copy(path, path_jsonl_raw)?;
+                DownloadCache {
+                    source: descriptor,
+                    fetched_at: now_timestamp(),
+                    ..Default::default()
+                }
+                .store(&sidecar)?;
+                if !quiet {
+                    pretty_println_at_path(&format!("{CHECK_C} Copied"), path_jsonl_raw);
+                }
+                Ok(())
+            }
+            DownloadSource::Snapshot { url, revision } => {
+                if path_jsonl_raw.exists() && cache.revision.as_deref() == Some(revision.as_str()) {
+                    if !quiet {
+                        pretty_println_at_path(&format!("{CHECK_C} Up to date"), path_jsonl_raw);
+                    }
+                    return Ok(());
+                }
+                if !quiet {
+                    println!("⬇ Downloading {url} (pinned to {revision})");
+                }
+                let response = ureq::get(url).call()?;
+                let reader = response.into_body().into_reader();
+                // We can't use gzip's ureq feature because there is no content-encoding in headers
+                // https://github.com/tatuylonen/wiktextract/issues/1482
+                let mut decoder = GzDecoder::new(reader);
+                let mut writer = BufWriter::new(File::create(path_jsonl_raw)?);
+                std::io::copy(&mut decoder, &mut writer)?;
+                drop(writer);
 
-        let mut writer = BufWriter::new(File::create(path_jsonl_raw)?);
-        std::io::copy(&mut decoder, &mut writer)?;
+                DownloadCache {
+                    source: url.clone(),
+                    fetched_at: now_timestamp(),
+                    revision: Some(revision.clone()),
+                    ..Default::default()
+                }
+                .store(&sidecar)?;
+                if !quiet {
+                    pretty_println_at_path(&format!("{CHECK_C} Downloaded"), path_jsonl_raw);
+                }
+                Ok(())
+            }
+            DownloadSource::Remote { url } => {
+                if !quiet {
+                    println!("⬇ Downloading {url}");
+                }
 
-        if !quiet {
-            pretty_println_at_path(&format!("{CHECK_C} Downloaded"), path_jsonl_raw);
+                let mut request = ureq::get(url);
+                // Only revalidate when the data file is still on disk to reuse on a 304.
+                if path_jsonl_raw.exists() && cache.has_validators() {
+                    if let Some(last_modified) = &cache.last_modified {
+                        request = request.header("If-Modified-Since", last_modified);
+                    }
+                    if let Some(etag) = &cache.etag {
+                        request = request.header("If-None-Match", etag);
+                    }
+                }
+
+                let response = request.call()?;
+
+                if response.status().as_u16() == 304 {
+                    if !quiet {
+                        pretty_println_at_path(&format!("{CHECK_C} Up to date"), path_jsonl_raw);
+                    }
+                    return Ok(());
+                }
+
+                // Capture the fresh validators before the body consumes the response.
+                let headers = response.headers();
+                let header_string = |name: &str| {
+                    headers
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string)
+                };
+                let new_cache = DownloadCache {
+                    source: url.clone(),
+                    fetched_at: now_timestamp(),
+                    last_modified: header_string("last-modified"),
+                    etag: header_string("etag"),
+                    revision: None,
+                };
+
+                let reader = response.into_body().into_reader();
+                // We can't use gzip's ureq feature because there is no content-encoding in headers
+                // https://github.com/tatuylonen/wiktextract/issues/1482
+                let mut decoder = GzDecoder::new(reader);
+                let mut writer = BufWriter::new(File::create(path_jsonl_raw)?);
+                std::io::copy(&mut decoder, &mut writer)?;
+                drop(writer);
+
+                new_cache.store(&sidecar)?;
+
+                if !quiet {
+                    pretty_println_at_path(&format!("{CHECK_C} Downloaded"), path_jsonl_raw);
+                }
+
+                Ok(())
+            }
         }
+    }
 
-        Ok(())
+    /// Download the raw jsonl from kaikki and write it to `path_jsonl_raw`.
+    ///
+    /// Skips the download when the server answers `304 Not Modified` to our conditional request and
+    /// the data file is still present. Does not write the .gz file to disk.
+    pub fn download_jsonl(
+        edition: EditionLang,
+        source: Lang,
+        path_jsonl_raw: &Path,
+        quiet: bool,
+    ) -> Result<()> {
+        let url = url_jsonl_raw_gz(edition, source);
+        fetch_source(&DownloadSource::Remote { url }, path_jsonl_raw, quiet)
     }
 }
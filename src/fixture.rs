@@ -0,0 +1,96 @@
+//! In-memory fixtures for hermetic benches and tests.
+//!
+//! A fixture is a single string split into several virtual files on `//- <relative/path>` marker
+//! lines, in the style of rust-analyzer's test fixtures. This lets a whole tiny edition — a handful
+//! of wiktextract JSONL entries, including deliberately malformed ones — live inline next to the
+//! test that uses it, with no files on disk and no `remove_dir_all` dance.
+//!
+//! A [`Fixture`] can be attached to [`ArgsOptions::fixture`](crate::cli::ArgsOptions::fixture);
+//! `make_dict` then reads its JSONL from the fixture instead of `root_dir`, so real-dump mode stays
+//! the default whenever the field is `None`.
+
+use crate::Map;
+use crate::lang::EditionLang;
+
+/// A set of virtual files parsed from a fixture string.
+#[derive(Debug, Clone, Default)]
+pub struct Fixture {
+    files: Map<String, String>,
+}
+
+impl Fixture {
+    /// Parse a fixture string into its virtual files.
+    ///
+    /// Lines beginning with `//-` open a new file; everything up to the next marker (or the end of
+    /// the string) is that file's content. Text before the first marker is ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut files = Map::default();
+        let mut current: Option<(String, String)> = None;
+
+        for line in text.lines() {
+            if let Some(path) = line.trim_start().strip_prefix("//-") {
+                if let Some((path, content)) = current.take() {
+                    files.insert(path, content);
+                }
+                current = Some((path.trim().to_string(), String::new()));
+            } else if let Some((_, content)) = current.as_mut() {
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+
+        if let Some((path, content)) = current.take() {
+            files.insert(path, content);
+        }
+
+        Self { files }
+    }
+
+    /// The content of a virtual file by its relative path.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// The JSONL bytes for an edition.
+    ///
+    /// Prefers a file whose path starts with the edition code (e.g. `el/...`); otherwise falls back
+    /// to the only file, matching the single-edition shape of most fixtures.
+    pub fn jsonl_for(&self, edition: EditionLang) -> Vec<u8> {
+        let prefix = format!("{edition}");
+        self.files
+            .iter()
+            .find(|(path, _)| path.starts_with(&prefix))
+            .or_else(|| self.files.first())
+            .map(|(_, content)| content.as_bytes().to_vec())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"
+//- el/raw.jsonl
+{"word":"λόγος","pos":"noun","lang_code":"el"}
+{"word":"bad json
+//- de/raw.jsonl
+{"word":"Wort","pos":"noun","lang_code":"de"}
+"#;
+
+    #[test]
+    fn splits_on_markers() {
+        let fixture = Fixture::parse(FIXTURE);
+        assert_eq!(fixture.files.len(), 2);
+        assert!(fixture.get("el/raw.jsonl").unwrap().contains("λόγος"));
+        assert!(fixture.get("de/raw.jsonl").unwrap().contains("Wort"));
+    }
+
+    #[test]
+    fn selects_jsonl_by_edition() {
+        let fixture = Fixture::parse(FIXTURE);
+        let de = String::from_utf8(fixture.jsonl_for(EditionLang::De)).unwrap();
+        assert!(de.contains("Wort"));
+        assert!(!de.contains("λόγος"));
+    }
+}
@@ -1,6 +1,3 @@
-//! This file was generated and should not be edited directly.
-//! The source code can be found at scripts/build.py
-
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -229,6 +226,372 @@ impl Lang {
             Self::Vi => "Vietnamese",
         }
     }
+
+    /// The language's native autonym, used as an alternative parse key.
+    pub const fn autonym(&self) -> &'static str {
+        match self {
+            Self::Sq => "shqip",
+            Self::Afb => "خليجي",
+            Self::Ar => "العربية",
+            Self::Apc => "شامي شمالي",
+            Self::Ajp => "شامي جنوبي",
+            Self::Aii => "ܣܘܪܝܬ",
+            Self::Bn => "বাংলা",
+            Self::Zh => "中文",
+            Self::Cs => "čeština",
+            Self::Da => "dansk",
+            Self::Nl => "Nederlands",
+            Self::En => "English",
+            Self::Enm => "Middle English",
+            Self::Ang => "Englisc",
+            Self::Eo => "Esperanto",
+            Self::Fi => "suomi",
+            Self::Fr => "français",
+            Self::Ka => "ქართული",
+            Self::De => "Deutsch",
+            Self::El => "Ελληνικά",
+            Self::Grc => "Ἀρχαία Ἑλληνική",
+            Self::He => "עברית",
+            Self::Hi => "हिन्दी",
+            Self::Hu => "magyar",
+            Self::Id => "Bahasa Indonesia",
+            Self::Ga => "Gaeilge",
+            Self::Sga => "Goídelc",
+            Self::It => "italiano",
+            Self::Ja => "日本語",
+            Self::Kn => "ಕನ್ನಡ",
+            Self::Kk => "қазақша",
+            Self::Km => "ភាសាខ្មែរ",
+            Self::Ko => "한국어",
+            Self::Ku => "kurdî",
+            Self::La => "Latina",
+            Self::Lv => "latviešu",
+            Self::Ms => "Bahasa Melayu",
+            Self::Mt => "Malti",
+            Self::Mr => "मराठी",
+            Self::Mn => "монгол",
+            Self::Nb => "bokmål",
+            Self::Nn => "nynorsk",
+            Self::Fa => "فارسی",
+            Self::Pl => "polski",
+            Self::Pt => "português",
+            Self::Ro => "română",
+            Self::Ru => "русский",
+            Self::Sh => "srpskohrvatski",
+            Self::Scn => "sicilianu",
+            Self::Sl => "slovenščina",
+            Self::Es => "español",
+            Self::Sv => "svenska",
+            Self::Tl => "Tagalog",
+            Self::Te => "తెలుగు",
+            Self::Th => "ไทย",
+            Self::Tr => "Türkçe",
+            Self::Uk => "українська",
+            Self::Ur => "اردو",
+            Self::Vi => "Tiếng Việt",
+        }
+    }
+
+    /// The babel package's language name, for LaTeX export.
+    ///
+    /// Returns `None` where babel has no stable mapping, so callers can fall back to a raw
+    /// `\foreignlanguage` with the BCP 47 tag instead.
+    pub const fn babel_name(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Sq => "albanian",
+            Self::Ar => "arabic",
+            Self::Bn => return None,
+            Self::Cs => "czech",
+            Self::Da => "danish",
+            Self::Nl => "dutch",
+            Self::En => "english",
+            Self::Eo => "esperanto",
+            Self::Fi => "finnish",
+            Self::Fr => "french",
+            Self::Ka => "georgian",
+            Self::De => "ngerman",
+            Self::El => "greek",
+            Self::He => "hebrew",
+            Self::Hu => "magyar",
+            Self::Id => "bahasai",
+            Self::Ga => "irish",
+            Self::It => "italian",
+            Self::La => "latin",
+            Self::Nb => "norsk",
+            Self::Nn => "nynorsk",
+            Self::Pl => "polish",
+            Self::Pt => "portuguese",
+            Self::Ro => "romanian",
+            Self::Ru => "russian",
+            Self::Sl => "slovenian",
+            Self::Es => "spanish",
+            Self::Sv => "swedish",
+            Self::Th => "thai",
+            Self::Tr => "turkish",
+            Self::Uk => "ukrainian",
+            _ => return None,
+        })
+    }
+
+    /// The polyglossia package's language name, for LaTeX export.
+    ///
+    /// Returns `None` where polyglossia has no stable mapping, so callers can fall back to a raw
+    /// `\foreignlanguage` with the BCP 47 tag instead.
+    pub const fn polyglossia_name(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Sq => "albanian",
+            Self::Ar => "arabic",
+            Self::Bn => "bengali",
+            Self::Cs => "czech",
+            Self::Da => "danish",
+            Self::Nl => "dutch",
+            Self::En => "english",
+            Self::Eo => "esperanto",
+            Self::Fi => "finnish",
+            Self::Fr => "french",
+            Self::Ka => "georgian",
+            Self::De => "german",
+            Self::El => "greek",
+            Self::Grc => "ancientgreek",
+            Self::He => "hebrew",
+            Self::Hi => "hindi",
+            Self::Hu => "magyar",
+            Self::Ga => "irish",
+            Self::It => "italian",
+            Self::La => "latin",
+            Self::Nb => "norwegian",
+            Self::Fa => "farsi",
+            Self::Pl => "polish",
+            Self::Pt => "portuguese",
+            Self::Ro => "romanian",
+            Self::Ru => "russian",
+            Self::Sl => "slovenian",
+            Self::Es => "spanish",
+            Self::Sv => "swedish",
+            Self::Th => "thai",
+            Self::Tr => "turkish",
+            Self::Uk => "ukrainian",
+            _ => return None,
+        })
+    }
+
+    /// Every supported language variant, in declaration order.
+    pub const ALL: &'static [Lang] = &[
+        Self::Sq,
+        Self::Afb,
+        Self::Ar,
+        Self::Apc,
+        Self::Ajp,
+        Self::Aii,
+        Self::Bn,
+        Self::Zh,
+        Self::Cs,
+        Self::Da,
+        Self::Nl,
+        Self::En,
+        Self::Enm,
+        Self::Ang,
+        Self::Eo,
+        Self::Fi,
+        Self::Fr,
+        Self::Ka,
+        Self::De,
+        Self::El,
+        Self::Grc,
+        Self::He,
+        Self::Hi,
+        Self::Hu,
+        Self::Id,
+        Self::Ga,
+        Self::Sga,
+        Self::It,
+        Self::Ja,
+        Self::Kn,
+        Self::Kk,
+        Self::Km,
+        Self::Ko,
+        Self::Ku,
+        Self::La,
+        Self::Lv,
+        Self::Ms,
+        Self::Mt,
+        Self::Mr,
+        Self::Mn,
+        Self::Nb,
+        Self::Nn,
+        Self::Fa,
+        Self::Pl,
+        Self::Pt,
+        Self::Ro,
+        Self::Ru,
+        Self::Sh,
+        Self::Scn,
+        Self::Sl,
+        Self::Es,
+        Self::Sv,
+        Self::Tl,
+        Self::Te,
+        Self::Th,
+        Self::Tr,
+        Self::Uk,
+        Self::Ur,
+        Self::Vi,
+    ];
+
+    /// The ISO 639-1 two-letter code, where the language has one.
+    ///
+    /// Returns `None` for languages that only exist in ISO 639-3 (e.g. `Afb`, `Grc`, `Scn`).
+    pub const fn iso639_1(&self) -> Option<&'static str> {
+        let code = match self {
+            Self::Sq => "sq",
+            Self::Ar => "ar",
+            Self::Bn => "bn",
+            Self::Zh => "zh",
+            Self::Cs => "cs",
+            Self::Da => "da",
+            Self::Nl => "nl",
+            Self::En => "en",
+            Self::Eo => "eo",
+            Self::Fi => "fi",
+            Self::Fr => "fr",
+            Self::Ka => "ka",
+            Self::De => "de",
+            Self::El => "el",
+            Self::He => "he",
+            Self::Hi => "hi",
+            Self::Hu => "hu",
+            Self::Id => "id",
+            Self::Ga => "ga",
+            Self::It => "it",
+            Self::Ja => "ja",
+            Self::Kn => "kn",
+            Self::Kk => "kk",
+            Self::Km => "km",
+            Self::Ko => "ko",
+            Self::Ku => "ku",
+            Self::La => "la",
+            Self::Lv => "lv",
+            Self::Ms => "ms",
+            Self::Mt => "mt",
+            Self::Mr => "mr",
+            Self::Mn => "mn",
+            Self::Nb => "nb",
+            Self::Nn => "nn",
+            Self::Fa => "fa",
+            Self::Pl => "pl",
+            Self::Pt => "pt",
+            Self::Ro => "ro",
+            Self::Ru => "ru",
+            Self::Sh => "sh",
+            Self::Sl => "sl",
+            Self::Es => "es",
+            Self::Sv => "sv",
+            Self::Tl => "tl",
+            Self::Te => "te",
+            Self::Th => "th",
+            Self::Tr => "tr",
+            Self::Uk => "uk",
+            Self::Ur => "ur",
+            Self::Vi => "vi",
+            // ISO 639-3 only
+            Self::Afb | Self::Apc | Self::Ajp | Self::Aii | Self::Enm | Self::Ang | Self::Grc
+            | Self::Sga | Self::Scn => return None,
+        };
+        Some(code)
+    }
+
+    /// The canonical ISO 639-3 three-letter code.
+    pub const fn iso639_3(&self) -> &'static str {
+        match self {
+            Self::Sq => "sqi",
+            Self::Afb => "afb",
+            Self::Ar => "ara",
+            Self::Apc => "apc",
+            Self::Ajp => "ajp",
+            Self::Aii => "aii",
+            Self::Bn => "ben",
+            Self::Zh => "zho",
+            Self::Cs => "ces",
+            Self::Da => "dan",
+            Self::Nl => "nld",
+            Self::En => "eng",
+            Self::Enm => "enm",
+            Self::Ang => "ang",
+            Self::Eo => "epo",
+            Self::Fi => "fin",
+            Self::Fr => "fra",
+            Self::Ka => "kat",
+            Self::De => "deu",
+            Self::El => "ell",
+            Self::Grc => "grc",
+            Self::He => "heb",
+            Self::Hi => "hin",
+            Self::Hu => "hun",
+            Self::Id => "ind",
+            Self::Ga => "gle",
+            Self::Sga => "sga",
+            Self::It => "ita",
+            Self::Ja => "jpn",
+            Self::Kn => "kan",
+            Self::Kk => "kaz",
+            Self::Km => "khm",
+            Self::Ko => "kor",
+            Self::Ku => "kur",
+            Self::La => "lat",
+            Self::Lv => "lav",
+            Self::Ms => "msa",
+            Self::Mt => "mlt",
+            Self::Mr => "mar",
+            Self::Mn => "mon",
+            Self::Nb => "nob",
+            Self::Nn => "nno",
+            Self::Fa => "fas",
+            Self::Pl => "pol",
+            Self::Pt => "por",
+            Self::Ro => "ron",
+            Self::Ru => "rus",
+            Self::Sh => "hbs",
+            Self::Scn => "scn",
+            Self::Sl => "slv",
+            Self::Es => "spa",
+            Self::Sv => "swe",
+            Self::Tl => "tgl",
+            Self::Te => "tel",
+            Self::Th => "tha",
+            Self::Tr => "tur",
+            Self::Uk => "ukr",
+            Self::Ur => "urd",
+            Self::Vi => "vie",
+        }
+    }
+
+    /// The dominant writing direction of the language, for bidi-aware terminal rendering.
+    ///
+    /// Right-to-left scripts (Arabic, Hebrew, Persian, …) need directional isolates or
+    /// bidi-aware wrapping so mixed Latin/RTL lines are not mangled.
+    pub const fn direction(&self) -> CharacterDirection {
+        match self {
+            Self::Ar
+            | Self::Afb
+            | Self::Apc
+            | Self::Ajp
+            | Self::Aii
+            | Self::He
+            | Self::Fa
+            | Self::Ur
+            | Self::Ku => CharacterDirection::Rtl,
+            _ => CharacterDirection::Ltr,
+        }
+    }
+}
+
+/// The dominant writing direction of a language's script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterDirection {
+    /// Left-to-right (Latin, Cyrillic, CJK, …).
+    Ltr,
+    /// Right-to-left (Arabic, Hebrew, …).
+    Rtl,
 }
 
 impl std::str::FromStr for Lang {
@@ -295,11 +658,130 @@ impl std::str::FromStr for Lang {
             "uk" => Ok(Self::Uk),
             "ur" => Ok(Self::Ur),
             "vi" => Ok(Self::Vi),
-            _ => Err(format!("unsupported iso code '{s}'\n{}", Self::help_supported_isos())),
+            // ISO 639-3 aliases for the two-letter languages, so either standard round-trips.
+            "sqi" => Ok(Self::Sq),
+            "ara" => Ok(Self::Ar),
+            "ben" => Ok(Self::Bn),
+            "zho" => Ok(Self::Zh),
+            "ces" => Ok(Self::Cs),
+            "dan" => Ok(Self::Da),
+            "nld" => Ok(Self::Nl),
+            "eng" => Ok(Self::En),
+            "epo" => Ok(Self::Eo),
+            "fin" => Ok(Self::Fi),
+            "fra" => Ok(Self::Fr),
+            "kat" => Ok(Self::Ka),
+            "deu" => Ok(Self::De),
+            "ell" => Ok(Self::El),
+            "heb" => Ok(Self::He),
+            "hin" => Ok(Self::Hi),
+            "hun" => Ok(Self::Hu),
+            "ind" => Ok(Self::Id),
+            "gle" => Ok(Self::Ga),
+            "ita" => Ok(Self::It),
+            "jpn" => Ok(Self::Ja),
+            "kan" => Ok(Self::Kn),
+            "kaz" => Ok(Self::Kk),
+            "khm" => Ok(Self::Km),
+            "kor" => Ok(Self::Ko),
+            "kur" => Ok(Self::Ku),
+            "lat" => Ok(Self::La),
+            "lav" => Ok(Self::Lv),
+            "msa" => Ok(Self::Ms),
+            "mlt" => Ok(Self::Mt),
+            "mar" => Ok(Self::Mr),
+            "mon" => Ok(Self::Mn),
+            "nob" => Ok(Self::Nb),
+            "nno" => Ok(Self::Nn),
+            "fas" => Ok(Self::Fa),
+            "pol" => Ok(Self::Pl),
+            "por" => Ok(Self::Pt),
+            "ron" => Ok(Self::Ro),
+            "rus" => Ok(Self::Ru),
+            "hbs" => Ok(Self::Sh),
+            "slv" => Ok(Self::Sl),
+            "spa" => Ok(Self::Es),
+            "swe" => Ok(Self::Sv),
+            "tgl" => Ok(Self::Tl),
+            "tel" => Ok(Self::Te),
+            "tha" => Ok(Self::Th),
+            "tur" => Ok(Self::Tr),
+            "ukr" => Ok(Self::Uk),
+            "urd" => Ok(Self::Ur),
+            "vie" => Ok(Self::Vi),
+            // Fall back to English display names and native autonyms.
+            _ => resolve_lang_name(s),
         }
     }
 }
 
+/// Normalize a name key for lookup: trim, lowercase, and fold Latin diacritics to ASCII.
+fn normalize_lang_key(s: &str) -> String {
+    s.trim()
+        .to_lowercase()
+        .chars()
+        .map(fold_accent)
+        .collect()
+}
+
+/// Map a single accented Latin character to its ASCII base; other characters pass through.
+fn fold_accent(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' => 'a',
+        'ç' => 'c',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' => 'o',
+        'š' => 's',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ž' => 'z',
+        other => other,
+    }
+}
+
+/// Resolve a language from its English name or native autonym, suggesting the nearest match on miss.
+fn resolve_lang_name(input: &str) -> Result<Lang, String> {
+    let key = normalize_lang_key(input);
+    for &lang in Lang::ALL {
+        if normalize_lang_key(lang.long()) == key || normalize_lang_key(lang.autonym()) == key {
+            return Ok(lang);
+        }
+    }
+
+    let nearest = Lang::ALL
+        .iter()
+        .map(|lang| (lang, levenshtein(&key, &normalize_lang_key(lang.long()))))
+        .min_by_key(|&(_, dist)| dist);
+    match nearest {
+        Some((lang, dist)) if dist <= 3 => Err(format!(
+            "unsupported language '{input}'; did you mean '{}'?",
+            lang.long()
+        )),
+        _ => Err(format!(
+            "unsupported language '{input}'\n{}",
+            Lang::help_supported_isos()
+        )),
+    }
+}
+
+/// Classic two-row Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
 impl std::fmt::Display for Lang {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let debug_str = format!("{self:?}");
@@ -307,6 +789,119 @@ impl std::fmt::Display for Lang {
     }
 }
 
+#[cfg(feature = "hyphenation")]
+impl Lang {
+    /// The `hyphenation` crate's [`Language`](hyphenation::Language) with published Liang patterns,
+    /// or `None` when no pattern set exists for the variant.
+    pub const fn hyphenation_language(&self) -> Option<hyphenation::Language> {
+        use hyphenation::Language as H;
+        Some(match self {
+            Self::Sq => H::Albanian,
+            Self::Bn => H::Bengali,
+            Self::Cs => H::Czech,
+            Self::Da => H::Danish,
+            Self::Nl => H::Dutch,
+            Self::En => H::EnglishUS,
+            Self::Eo => H::Esperanto,
+            Self::Fi => H::Finnish,
+            Self::Fr => H::French,
+            Self::Ka => H::Georgian,
+            Self::De => H::German1996,
+            Self::El => H::GreekMono,
+            Self::Grc => H::GreekAncient,
+            Self::Hi => H::Hindi,
+            Self::Hu => H::Hungarian,
+            Self::Id => H::Indonesian,
+            Self::Ga => H::Irish,
+            Self::It => H::Italian,
+            Self::La => H::Latin,
+            Self::Lv => H::Latvian,
+            Self::Mn => H::Mongolian,
+            Self::Nb => H::NorwegianBokmal,
+            Self::Nn => H::NorwegianNynorsk,
+            Self::Pl => H::Polish,
+            Self::Pt => H::Portuguese,
+            Self::Ro => H::Romanian,
+            Self::Ru => H::Russian,
+            Self::Sh => H::SerbianLatin,
+            Self::Sl => H::Slovenian,
+            Self::Es => H::Spanish,
+            Self::Sv => H::Swedish,
+            Self::Th => H::Thai,
+            Self::Tr => H::Turkish,
+            Self::Uk => H::Ukrainian,
+            _ => return None,
+        })
+    }
+
+    /// Wrap `text` to `width` columns, breaking long words at valid hyphenation points.
+    ///
+    /// Falls back to whitespace-only wrapping when no hyphenation patterns are available for the
+    /// language.
+    pub fn hyphenate_wrap(&self, text: &str, width: usize) -> String {
+        use hyphenation::{Hyphenator, Load, Standard};
+
+        let dict = self
+            .hyphenation_language()
+            .and_then(|lang| Standard::from_embedded(lang).ok());
+
+        let mut out = String::new();
+        let mut col = 0;
+        for word in text.split_whitespace() {
+            let sep = usize::from(col > 0);
+            if col + sep + word.chars().count() <= width {
+                if col > 0 {
+                    out.push(' ');
+                }
+                out.push_str(word);
+                col += sep + word.chars().count();
+                continue;
+            }
+
+            // The word overflows the current line; try to split it at a hyphenation point.
+            if let Some(dict) = &dict {
+                if let Some((head, tail)) = split_at_break(dict, word, width.saturating_sub(col + 1))
+                {
+                    if col > 0 {
+                        out.push(' ');
+                    }
+                    out.push_str(&head);
+                    out.push('-');
+                    out.push('\n');
+                    out.push_str(&tail);
+                    col = tail.chars().count();
+                    continue;
+                }
+            }
+
+            if col > 0 {
+                out.push('\n');
+            }
+            out.push_str(word);
+            col = word.chars().count();
+        }
+        out
+    }
+}
+
+/// Split `word` at the last hyphenation point that keeps the head (plus its hyphen) within `budget`.
+#[cfg(feature = "hyphenation")]
+fn split_at_break(
+    dict: &hyphenation::Standard,
+    word: &str,
+    budget: usize,
+) -> Option<(String, String)> {
+    use hyphenation::Hyphenator;
+
+    let breaks = dict.hyphenate(word).breaks;
+    let chosen = breaks
+        .iter()
+        .copied()
+        .filter(|&byte_idx| word[..byte_idx].chars().count() + 1 <= budget)
+        .next_back()?;
+    Some((word[..chosen].to_string(), word[chosen..].to_string()))
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Edition {
     /// All editions
@@ -493,3 +1088,189 @@ impl std::fmt::Display for EditionLang {
         write!(f, "{}", debug_str.to_lowercase())
     }
 }
+
+// --- BCP 47 language tags -------------------------------------------------
+//
+// A handful of editions genuinely need script/region disambiguation (`zh-Hant` vs `zh-Hans`,
+// `sh-Latn` vs `sh-Cyrl`, `pt-BR` vs `pt-PT`). `LangTag` keeps the bare `Lang` alongside the
+// optional finer-grained subtags, while the plain `Lang`/`EditionLang` parsers stay untouched.
+
+/// A BCP 47 script subtag (ISO 15924), restricted to the scripts our editions use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Latn,
+    Cyrl,
+    Hant,
+    Hans,
+}
+
+impl Script {
+    const fn code(self) -> &'static str {
+        match self {
+            Self::Latn => "Latn",
+            Self::Cyrl => "Cyrl",
+            Self::Hant => "Hant",
+            Self::Hans => "Hans",
+        }
+    }
+}
+
+impl std::str::FromStr for Script {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "latn" => Ok(Self::Latn),
+            "cyrl" => Ok(Self::Cyrl),
+            "hant" => Ok(Self::Hant),
+            "hans" => Ok(Self::Hans),
+            _ => Err(format!("unsupported script subtag '{s}'")),
+        }
+    }
+}
+
+impl std::fmt::Display for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// A BCP 47 region subtag (ISO 3166-1 alpha-2), restricted to the regions our editions use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Br,
+    Pt,
+}
+
+impl Region {
+    const fn code(self) -> &'static str {
+        match self {
+            Self::Br => "BR",
+            Self::Pt => "PT",
+        }
+    }
+}
+
+impl std::str::FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "BR" => Ok(Self::Br),
+            "PT" => Ok(Self::Pt),
+            _ => Err(format!("unsupported region subtag '{s}'")),
+        }
+    }
+}
+
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// A language tag carrying an optional script and region, e.g. `zh-Hant` or `pt-BR`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LangTag {
+    pub lang: Lang,
+    pub script: Option<Script>,
+    pub region: Option<Region>,
+}
+
+impl LangTag {
+    /// Parse a BCP 47 tag into its language / script / region subtags.
+    ///
+    /// Subtags are distinguished by shape: four letters is a script, two letters (or a plain bare
+    /// code) is the primary language or a region. Separators may be `-` or `_`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut subtags = s.split(['-', '_']);
+        let primary = subtags
+            .next()
+            .ok_or_else(|| "empty language tag".to_string())?;
+        let lang: Lang = primary.parse()?;
+
+        let mut script = None;
+        let mut region = None;
+        for subtag in subtags {
+            if subtag.len() == 4 {
+                script = Some(subtag.parse()?);
+            } else {
+                region = Some(subtag.parse()?);
+            }
+        }
+
+        Ok(Self {
+            lang,
+            script,
+            region,
+        })
+    }
+}
+
+impl std::str::FromStr for LangTag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::fmt::Display for LangTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lang)?;
+        if let Some(script) = self.script {
+            write!(f, "-{script}")?;
+        }
+        if let Some(region) = self.region {
+            write!(f, "-{region}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_script_and_region() {
+        let tag = LangTag::parse("zh-Hant").unwrap();
+        assert_eq!(tag.lang, Lang::Zh);
+        assert_eq!(tag.script, Some(Script::Hant));
+        assert_eq!(tag.region, None);
+
+        let tag = LangTag::parse("pt-BR").unwrap();
+        assert_eq!(tag.lang, Lang::Pt);
+        assert_eq!(tag.region, Some(Region::Br));
+
+        // An underscore separator works too.
+        let tag = LangTag::parse("sh_Cyrl").unwrap();
+        assert_eq!(tag.lang, Lang::Sh);
+        assert_eq!(tag.script, Some(Script::Cyrl));
+    }
+
+    #[test]
+    fn resolves_names_and_autonyms() {
+        assert_eq!("french".parse::<Lang>().unwrap(), Lang::Fr);
+        assert_eq!("Ancient Greek".parse::<Lang>().unwrap(), Lang::Grc);
+        // Autonym, and the accent-folded spelling, both resolve.
+        assert_eq!("français".parse::<Lang>().unwrap(), Lang::Fr);
+        assert_eq!("Francais".parse::<Lang>().unwrap(), Lang::Fr);
+        // ISO codes still take the fast path.
+        assert_eq!("fr".parse::<Lang>().unwrap(), Lang::Fr);
+    }
+
+    #[test]
+    fn suggests_nearest_name() {
+        let err = "frensh".parse::<Lang>().unwrap_err();
+        assert!(err.contains("French"), "{err}");
+    }
+
+    #[test]
+    fn bare_code_round_trips() {
+        let tag = LangTag::parse("nb").unwrap();
+        assert_eq!(tag.lang, Lang::Nb);
+        assert_eq!(tag.to_string(), "nb");
+        assert_eq!(LangTag::parse("zh-Hant").unwrap().to_string(), "zh-Hant");
+    }
+}
@@ -2,10 +2,13 @@ pub mod cli;
 pub mod diagnostic;
 pub mod dict;
 pub mod download;
+pub mod fixture;
 pub mod lang;
 pub mod locale;
+pub mod manifest;
 pub mod models;
 pub mod path;
+pub mod scope;
 pub mod tags;
 pub mod utils;
 
@@ -31,6 +34,7 @@ use crate::lang::{EditionLang, Lang};
 use crate::models::kaikki::WordEntry;
 use crate::models::yomitan::YomitanEntry;
 use crate::path::PathManager;
+use crate::scope::entry_has_any_scope;
 use crate::tags::get_tag_bank_as_tag_info;
 use crate::utils::{
     CHECK_C, pretty_print_at_path, pretty_println_at_path, skip_because_file_exists,
@@ -349,6 +353,19 @@ pub trait Dictionary {
     }
 }
 
+/// One lock per raw-jsonl path, so concurrent jobs (e.g. [`crate::manifest`]) sharing an edition
+/// wait on each other instead of racing to fetch the same `*-extract.jsonl` twice.
+fn download_lock_for(path: &Path) -> std::sync::Arc<std::sync::Mutex<()>> {
+    static LOCKS: std::sync::OnceLock<
+        std::sync::Mutex<Map<PathBuf, std::sync::Arc<std::sync::Mutex<()>>>>,
+    > = std::sync::OnceLock::new();
+    let mut locks = LOCKS.get_or_init(Default::default).lock().unwrap();
+    locks
+        .entry(path.to_path_buf())
+        .or_insert_with(|| std::sync::Arc::new(std::sync::Mutex::new(())))
+        .clone()
+}
+
 fn find_or_download_jsonl(
     edition: EditionLang,
     lang: Lang,
@@ -361,19 +378,68 @@ fn find_or_download_jsonl(
         if !options.quiet {
             skip_because_file_exists("download", pbuf);
         }
-        Ok(pbuf.clone())
-    } else {
-        let path_jsonl_raw_of_download = paths.last().unwrap();
-        #[cfg(feature = "html")]
-        download_jsonl(edition, lang, path_jsonl_raw_of_download, options.quiet)?;
-        Ok(path_jsonl_raw_of_download.clone())
+        return Ok(pbuf.clone());
+    }
+
+    let path_jsonl_raw_of_download = paths.last().unwrap();
+    let lock = download_lock_for(path_jsonl_raw_of_download);
+    let _guard = lock.lock().unwrap();
+
+    // Another job may have fetched it while we were waiting for the lock.
+    if !options.redownload && path_jsonl_raw_of_download.exists() {
+        if !options.quiet {
+            skip_because_file_exists("download", path_jsonl_raw_of_download);
+        }
+        return Ok(path_jsonl_raw_of_download.clone());
     }
+
+    #[cfg(feature = "html")]
+    download_jsonl(edition, lang, path_jsonl_raw_of_download, options.quiet)?;
+    Ok(path_jsonl_raw_of_download.clone())
 }
 
 const CONSOLE_PRINT_INTERVAL: i32 = 10000;
 
+/// Wall-clock spent in each stage of a single `make_dict` run.
+///
+/// Populated by [`make_dict_timed`] so a regression in `el_el` can be attributed to parsing,
+/// transformation, deduplication or serialization rather than guessed at.
+#[derive(Debug, Default, Clone)]
+pub struct PipelineTimings {
+    /// Opening (and, with the `html` feature, downloading/decompressing) the raw dumps.
+    pub read: std::time::Duration,
+    /// `serde_json` decoding of accepted lines.
+    pub parse: std::time::Duration,
+    /// `preprocess` + `process` (term/gloss transform).
+    pub transform: std::time::Duration,
+    /// `postprocess` (sort/dedup/merge).
+    pub postprocess: std::time::Duration,
+    /// `to_yomitan` plus bank serialization.
+    pub serialize: std::time::Duration,
+}
+
+impl PipelineTimings {
+    fn log(&self) {
+        println!(
+            "Stage timings: read={:.3?} parse={:.3?} transform={:.3?} postprocess={:.3?} serialize={:.3?}",
+            self.read, self.parse, self.transform, self.postprocess, self.serialize
+        );
+    }
+}
+
 pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager) -> Result<()> {
+    make_dict_timed(dict, options, pm).map(|_| ())
+}
+
+/// Like [`make_dict`] but returns per-stage [`PipelineTimings`] (and logs them unless `quiet`, or
+/// unless no entries were found, in which case it returns before postprocessing).
+pub fn make_dict_timed<D: Dictionary>(
+    dict: D,
+    options: &ArgsOptions,
+    pm: &PathManager,
+) -> Result<PipelineTimings> {
     let (edition_pm, source_pm, target_pm) = pm.langs();
+    let mut timings = PipelineTimings::default();
 
     pm.setup_dirs()?;
 
@@ -383,12 +449,17 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
     let mut entries = D::I::default();
 
     for (edition, paths) in pm.paths_jsonl_raw() {
-        let path_jsonl_raw = find_or_download_jsonl(edition, source_pm, &paths, options)?;
-        tracing::debug!("path_jsonl_raw: {}", path_jsonl_raw.display());
-
-        let reader_path = &path_jsonl_raw;
-        let reader_file = File::open(reader_path)?;
-        let mut reader = BufReader::with_capacity(capacity, reader_file);
+        let read_start = std::time::Instant::now();
+        // Hermetic mode: read the edition's JSONL straight from the in-memory fixture.
+        let mut reader: Box<dyn BufRead> = if let Some(fixture) = &options.fixture {
+            Box::new(std::io::Cursor::new(fixture.jsonl_for(edition)))
+        } else {
+            let path_jsonl_raw = find_or_download_jsonl(edition, source_pm, &paths, options)?;
+            tracing::debug!("path_jsonl_raw: {}", path_jsonl_raw.display());
+            let reader_file = File::open(&path_jsonl_raw)?;
+            Box::new(BufReader::with_capacity(capacity, reader_file))
+        };
+        timings.read += read_start.elapsed();
 
         let mut cached_lines = Vec::new();
         let mut line_count = 0;
@@ -402,8 +473,10 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
 
             line_count += 1;
 
+            let parse_start = std::time::Instant::now();
             let mut word_entry: WordEntry =
                 serde_json::from_slice(&line).with_context(|| "Error decoding JSON @ make_dict")?;
+            timings.parse += parse_start.elapsed();
 
             if !options.quiet && line_count % CONSOLE_PRINT_INTERVAL == 0 {
                 print!("Processed {line_count} lines...\r");
@@ -413,7 +486,7 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
             if options
                 .reject
                 .iter()
-                .any(|(k, v)| k.field_value(&word_entry) == v)
+                .any(|(k, v)| k.matches(&word_entry, v, options.normalization))
             {
                 continue;
             }
@@ -421,11 +494,22 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
             if !options
                 .filter
                 .iter()
-                .all(|(k, v)| k.field_value(&word_entry) == v)
+                .all(|(k, v)| k.matches(&word_entry, v, options.normalization))
             {
                 continue;
             }
 
+            if !options.scope_tag_filter.is_empty()
+                && !entry_has_any_scope(&word_entry, &options.scope_tag_filter)
+            {
+                continue;
+            }
+
+            // Drop senses in excluded usage registers; skip the entry if nothing glossed survives.
+            if !word_entry.retain_senses_in_scope(&options.exclude_scope) {
+                continue;
+            }
+
             if options.cache_filter {
                 cached_lines.extend(line.clone());
             }
@@ -435,6 +519,7 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
                 break;
             }
 
+            let transform_start = std::time::Instant::now();
             dict.preprocess(
                 edition,
                 source_pm,
@@ -445,17 +530,20 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
             );
 
             dict.process(edition, source_pm, target_pm, &word_entry, &mut entries);
+            timings.transform += transform_start.elapsed();
         }
 
         if !options.quiet {
             println!("Processed {line_count} lines. Accepted {accepted_count} lines.");
         }
 
-        if options.cache_filter {
+        // The fixture is read-only; there is no raw file to rewrite the filtered lines back into.
+        if options.cache_filter && options.fixture.is_none() {
+            let path_jsonl_raw = find_or_download_jsonl(edition, source_pm, &paths, options)?;
             let mut writer_file = std::fs::OpenOptions::new()
                 .write(true)
                 .truncate(true)
-                .open(reader_path)?;
+                .open(&path_jsonl_raw)?;
             writer_file.write_all(&cached_lines)?;
         }
     }
@@ -465,17 +553,20 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
     }
 
     if entries.is_empty() {
-        return Ok(());
+        return Ok(timings);
     }
 
+    let postprocess_start = std::time::Instant::now();
     dict.postprocess(&mut entries);
     // println!("Postprocessed down to {} entries", entries.len());
+    timings.postprocess += postprocess_start.elapsed();
 
     if options.save_temps && dict.write_ir() {
         entries.write(pm, options)?;
     }
 
     if !options.skip_yomitan {
+        let serialize_start = std::time::Instant::now();
         let mut diagnostics = Diagnostics::default();
 
         let labelled_entries = dict.to_yomitan(
@@ -493,9 +584,125 @@ pub fn make_dict<D: Dictionary>(dict: D, options: &ArgsOptions, pm: &PathManager
         dict.write_diagnostics(pm, &diagnostics)?;
 
         write_yomitan(source_pm, target_pm, options, pm, &labelled_entries)?;
+        timings.serialize += serialize_start.elapsed();
     }
 
-    Ok(())
+    if !options.quiet {
+        timings.log();
+    }
+
+    Ok(timings)
+}
+
+/// A lazy stream of per-entry intermediate representations.
+///
+/// Where [`make_dict`] buffers the whole dictionary before postprocessing and writing, this pulls
+/// one accepted line at a time — parsing, preprocessing and processing it into a fresh `D::I` — so
+/// a consumer can begin emitting Yomitan banks incrementally, and a bench can measure *time to
+/// first entry* by pulling a single item off the stream and stopping.
+///
+/// Only the first edition's source is streamed (fixture or on-disk JSONL); the merge-across-editions
+/// path stays in the batch builder.
+pub struct DictStream<'a, D: Dictionary> {
+    dict: D,
+    options: &'a ArgsOptions,
+    edition: EditionLang,
+    source: Lang,
+    target: Lang,
+    reader: Box<dyn BufRead + 'a>,
+    line: Vec<u8>,
+}
+
+/// Build a [`DictStream`] over the first edition's entries.
+pub fn stream_dict<'a, D: Dictionary>(
+    dict: D,
+    options: &'a ArgsOptions,
+    pm: &PathManager,
+) -> Result<DictStream<'a, D>> {
+    let (_, source, target) = pm.langs();
+    let (edition, paths) = pm
+        .paths_jsonl_raw()
+        .into_iter()
+        .next()
+        .context("no edition to stream")?;
+
+    let reader: Box<dyn BufRead> = if let Some(fixture) = &options.fixture {
+        Box::new(std::io::Cursor::new(fixture.jsonl_for(edition)))
+    } else {
+        let path_jsonl_raw = find_or_download_jsonl(edition, source, &paths, options)?;
+        Box::new(BufReader::new(File::open(&path_jsonl_raw)?))
+    };
+
+    Ok(DictStream {
+        dict,
+        options,
+        edition,
+        source,
+        target,
+        reader,
+        line: Vec::with_capacity(1 << 10),
+    })
+}
+
+impl<D: Dictionary> Iterator for DictStream<'_, D> {
+    type Item = Result<D::I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => return None, // EOF
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+
+            let mut word_entry: WordEntry = match serde_json::from_slice(&self.line) {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            let opts = self.options;
+            if opts
+                .reject
+                .iter()
+                .any(|(k, v)| k.matches(&word_entry, v, opts.normalization))
+            {
+                continue;
+            }
+            if !opts
+                .filter
+                .iter()
+                .all(|(k, v)| k.matches(&word_entry, v, opts.normalization))
+            {
+                continue;
+            }
+            if !opts.scope_tag_filter.is_empty()
+                && !entry_has_any_scope(&word_entry, &opts.scope_tag_filter)
+            {
+                continue;
+            }
+            if !word_entry.retain_senses_in_scope(&opts.exclude_scope) {
+                continue;
+            }
+
+            let mut irs = D::I::default();
+            self.dict
+                .preprocess(self.edition, self.source, self.target, &mut word_entry, opts, &mut irs);
+            self.dict
+                .process(self.edition, self.source, self.target, &word_entry, &mut irs);
+            return Some(Ok(irs));
+        }
+    }
+}
+
+/// Adapt a [`DictStream`] into an async [`Stream`](futures::Stream) of entries.
+#[cfg(feature = "async")]
+pub fn stream_dict_async<'a, D: Dictionary>(
+    dict: D,
+    options: &'a ArgsOptions,
+    pm: &PathManager,
+) -> Result<impl futures::Stream<Item = Result<D::I>> + 'a> {
+    Ok(futures::stream::iter(stream_dict(dict, options, pm)?))
 }
 
 #[cfg(test)]
@@ -640,6 +847,9 @@ mod tests {
 
         tracing::debug!("Found {} cases: {cases:?}", cases.len());
 
+        // Open once: gix's repository discovery is not free, and every case below diffs against it.
+        let repo = gix::ThreadSafeRepository::open(".").expect("current directory is a git repo");
+
         // failfast
         // main
         for (source, target) in &cases {
@@ -649,7 +859,7 @@ mod tests {
             let args = fixture_main_args(target, *source, target, &fixture_dir);
             let pm = PathManager::new(DictionaryType::Main, &args);
 
-            if let Err(e) = shapshot_main(&args.options, &pm) {
+            if let Err(e) = shapshot_main(&args.options, &pm, &repo) {
                 panic!("({source}): {e}");
             }
         }
@@ -694,31 +904,148 @@ mod tests {
         Ok(())
     }
 
-    /// Read the expected result in the snapshot first, then git diff
-    fn shapshot_main(options: &ArgsOptions, pm: &PathManager) -> Result<()> {
+    /// Read the expected result in the snapshot first, then diff it against the fresh output.
+    ///
+    /// Set `KTY_SNAPSHOT_DIFF=structural` to get a semantic, JSON-aware changeset instead of the
+    /// default in-process unified diff (see [`check_structural_diff`]).
+    fn shapshot_main(
+        options: &ArgsOptions,
+        pm: &PathManager,
+        repo: &gix::ThreadSafeRepository,
+    ) -> Result<()> {
         delete_previous_output(pm)?;
         make_dict(DMain, options, pm)?;
-        check_git_diff(pm)?;
+        if std::env::var("KTY_SNAPSHOT_DIFF").ok().as_deref() == Some("structural") {
+            check_structural_diff(pm)?;
+        } else {
+            check_git_diff(pm, repo)?;
+        }
         Ok(())
     }
 
-    /// Run git --diff for charges in the generated json
-    fn check_git_diff(pm: &PathManager) -> Result<()> {
-        let output = std::process::Command::new("git")
-            .args([
-                "diff",
-                "--color=always",
-                "--unified=0", // show 0 context lines
-                "--",
-                // we don't care about changes in tidy files
-                &pm.dir_temp_dict().to_string_lossy(),
-            ])
-            .output()?;
-        if !output.stdout.is_empty() {
-            eprintln!("{}", String::from_utf8_lossy(&output.stdout));
+    /// Look up the `HEAD` blob for `path` in `repo`, if any (`None` for an untracked/new file).
+    fn read_head_blob(repo: &gix::Repository, path: &Path) -> Option<Vec<u8>> {
+        let relative = path.strip_prefix(repo.work_dir()?).unwrap_or(path);
+        let tree = repo.head_commit().ok()?.tree().ok()?;
+        let entry = tree.lookup_entry_by_path(relative).ok()??;
+        Some(entry.object().ok()?.data.clone())
+    }
+
+    /// Minimal unified diff: lines only in `old` prefixed `-`, lines only in `new` prefixed `+`, no
+    /// surrounding context. Good enough for the small generated snapshot fixtures this harness
+    /// compares.
+    fn unified_diff(old: &str, new: &str) -> String {
+        let old_lines: Vec<&str> = old.lines().collect();
+        let new_lines: Vec<&str> = new.lines().collect();
+        let (n, m) = (old_lines.len(), new_lines.len());
+
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_lines[i] == new_lines[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut out = String::new();
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_lines[i] == new_lines[j] {
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                out.push_str(&format!("-{}\n", old_lines[i]));
+                i += 1;
+            } else {
+                out.push_str(&format!("+{}\n", new_lines[j]));
+                j += 1;
+            }
+        }
+        for line in &old_lines[i..] {
+            out.push_str(&format!("-{line}\n"));
+        }
+        for line in &new_lines[j..] {
+            out.push_str(&format!("+{line}\n"));
+        }
+        out
+    }
+
+    /// Diff the generated dict directory against its `HEAD` snapshot entirely in-process via
+    /// `gix`, instead of shelling out to a `git` binary on `PATH`.
+    fn check_git_diff(pm: &PathManager, repo: &gix::ThreadSafeRepository) -> Result<()> {
+        let repo = repo.to_thread_local();
+        let mut rendered = String::new();
+
+        for entry in fs::read_dir(pm.dir_temp_dict())?.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            let new_contents = fs::read_to_string(&path)?;
+            let old_contents = read_head_blob(&repo, &path)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default();
+
+            if old_contents != new_contents {
+                rendered.push_str(&format!("--- a/{}\n", path.display()));
+                rendered.push_str(&format!("+++ b/{}\n", path.display()));
+                rendered.push_str(&unified_diff(&old_contents, &new_contents));
+            }
+        }
+
+        if !rendered.is_empty() {
+            eprintln!("{rendered}");
             anyhow::bail!("changes!")
         }
 
         Ok(())
     }
+
+    /// JSON-aware alternative to [`check_git_diff`]: for each `*.json` bank under
+    /// `pm.dir_temp_dict()`, diff the committed (`git show HEAD:...`) and freshly generated
+    /// versions with [`crate::dict::diff_bank`] and fail only on real content deltas, reporting a
+    /// readable summary rather than a wall of `+`/`-` lines.
+    fn check_structural_diff(pm: &PathManager) -> Result<()> {
+        let dir = pm.dir_temp_dict();
+        let mut any_changes = false;
+
+        for entry in fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let new_contents = fs::read_to_string(&path)?;
+            let git_path = format!("HEAD:{}", path.to_string_lossy());
+            let output = std::process::Command::new("git").args(["show", &git_path]).output()?;
+            let old_contents = String::from_utf8_lossy(&output.stdout);
+
+            let old_bank = crate::dict::read_bank(&old_contents);
+            let new_bank = crate::dict::read_bank(&new_contents);
+            let diff = crate::dict::diff_bank(&old_bank, &new_bank);
+
+            if !diff.is_empty() {
+                any_changes = true;
+                eprintln!("{}: {}", path.display(), diff.summary());
+                for (key, paths) in &diff.changed {
+                    eprintln!("  ~ {key}: {}", paths.join(", "));
+                }
+                for key in &diff.added {
+                    eprintln!("  + {key}");
+                }
+                for key in &diff.removed {
+                    eprintln!("  - {key}");
+                }
+            }
+        }
+
+        if any_changes {
+            anyhow::bail!("changes!")
+        }
+        Ok(())
+    }
 }
@@ -1,11 +1,16 @@
 use anyhow::Result;
 
-use kty::cli::{Cli, Command, Langs, SimpleArgs};
-use kty::dict::{DGlossary, DGlossaryExtended, DIpa, DIpaMerged, DMain};
-use kty::download::download_jsonl;
+use kty::cli::{BankKind, Cli, Command, Langs, SimpleArgs};
+use kty::dict::{
+    DFormOf, DForms, DGlossary, DGlossaryExtended, DGlossaryMatrix, DHyphenation, DIpa, DIpaMerged,
+    DMain, tidy,
+};
+use kty::download::{DownloadSource, fetch_source, url_jsonl_raw_gz};
 use kty::lang::{EditionLang, Lang};
+use kty::manifest::{self, Manifest};
+use kty::models::yomitan;
 use kty::path::{DictionaryType, PathManager};
-use kty::utils::skip_because_file_exists;
+use kty::utils::{CHECK_C, FAIL_C};
 use kty::{make_dict, setup_tracing};
 
 fn run_command(cmd: &Command) -> Result<()> {
@@ -31,6 +36,22 @@ fn run_command(cmd: &Command) -> Result<()> {
             let pm = PathManager::new(DictionaryType::IpaMerged, args);
             make_dict(DIpaMerged, &args.options, &pm)
         }
+        Command::FormOf(args) => {
+            let pm = PathManager::new(DictionaryType::FormOf, args);
+            make_dict(DFormOf, &args.options, &pm)
+        }
+        Command::GlossaryMatrix(args) => {
+            let pm = PathManager::new(DictionaryType::GlossaryMatrix, args);
+            make_dict(DGlossaryMatrix, &args.options, &pm)
+        }
+        Command::Hyphenation(args) => {
+            let pm = PathManager::new(DictionaryType::Hyphenation, args);
+            make_dict(DHyphenation, &args.options, &pm)
+        }
+        Command::Forms(args) => {
+            let pm = PathManager::new(DictionaryType::Forms, args);
+            make_dict(DForms, &args.options, &pm)
+        }
         Command::Download(args) => {
             let pm = PathManager::new(DictionaryType::Main, args);
             let langs = args.langs();
@@ -38,12 +59,85 @@ fn run_command(cmd: &Command) -> Result<()> {
             let edition_lang: EditionLang = langs.edition().try_into().unwrap();
             let opath = pm.path_jsonl_raw(edition_lang, source);
 
-            if opath.exists() {
-                skip_because_file_exists("download", &opath);
+            let download_source = if let Some(path) = args.options.download_local.clone() {
+                DownloadSource::Local { path }
+            } else {
+                let url = url_jsonl_raw_gz(edition_lang, source);
+                match args.options.download_revision.clone() {
+                    Some(revision) => DownloadSource::Snapshot { url, revision },
+                    None => DownloadSource::Remote { url },
+                }
+            };
+
+            let _ = std::fs::create_dir(pm.dir_kaik());
+            fetch_source(&download_source, &opath, args.options.quiet)
+        }
+        Command::Tidy(args) => {
+            let pm = PathManager::new(DictionaryType::Main, args);
+            let budget_bytes = args.options.tidy_bank_size_budget_mb * 1024 * 1024;
+            let report = tidy(&pm.dir_temp_dict(), budget_bytes)?;
+            report.print_summary();
+            if report.ok() {
+                Ok(())
+            } else {
+                anyhow::bail!("tidy checks failed")
+            }
+        }
+        Command::Build(args) => {
+            let manifest = Manifest::read(&args.manifest)?;
+            let reports = manifest::run(&manifest, &args.options)?;
+            if reports.is_empty() {
+                anyhow::bail!("no jobs matched the manifest's only/except selection");
+            }
+
+            let mut failed = 0;
+            for report in &reports {
+                match &report.result {
+                    Result::Ok(()) => println!("{CHECK_C} {}", report.name),
+                    Err(err) => {
+                        failed += 1;
+                        println!("{FAIL_C} {}: {err:#}", report.name);
+                    }
+                }
+            }
+            println!(
+                "{}/{} dictionaries built",
+                reports.len() - failed,
+                reports.len()
+            );
+
+            if failed == 0 {
+                Ok(())
+            } else {
+                anyhow::bail!("{failed} of {} dictionaries failed to build", reports.len())
+            }
+        }
+        Command::ValidateBank(args) => {
+            let file = std::fs::File::open(&args.path)?;
+            let errors = match args.kind {
+                BankKind::Term => match yomitan::validate_term_bank(file) {
+                    Result::Ok(entries) => {
+                        println!("{CHECK_C} {} entries validated", entries.len());
+                        Vec::new()
+                    }
+                    Err(errors) => errors,
+                },
+                BankKind::Tag => match yomitan::validate_tag_bank(file) {
+                    Result::Ok(entries) => {
+                        println!("{CHECK_C} {} entries validated", entries.len());
+                        Vec::new()
+                    }
+                    Err(errors) => errors,
+                },
+            };
+
+            if errors.is_empty() {
                 Ok(())
             } else {
-                let _ = std::fs::create_dir(pm.dir_kaik());
-                download_jsonl(edition_lang, source, &opath, args.options.quiet)
+                for (index, error) in &errors {
+                    println!("{FAIL_C} entry {index}: {error}");
+                }
+                anyhow::bail!("{} of the bank's entries failed to validate", errors.len())
             }
         }
         Command::Iso(args) => {
@@ -0,0 +1,255 @@
+//! `build` subcommand: a manifest-driven alternative to invoking the binary once per dictionary.
+//!
+//! A [`Manifest`] lists the dictionaries to build as a flat array of [`ManifestJob`]s (dict type,
+//! edition, and the other language), plus an optional `only`/`except` selection mirroring Helix's
+//! `use-grammars` convention. [`run`] expands and validates every selected job through the same
+//! [`crate::cli::prepare_command`] path the CLI itself uses, up front and before any build starts,
+//! then drives [`make_dict`] across all of them concurrently on a thread pool sized to the
+//! available CPUs, returning one [`JobReport`] per job. Jobs that share an edition's raw jsonl
+//! dedupe and lock around the download in `find_or_download_jsonl`, so it is fetched at most once
+//! even when several jobs race for it.
+//!
+//! Only dictionary types that take a single (edition, other language) pair can be expressed as a
+//! job today: [`Main`](ManifestDictType::Main), [`Glossary`](ManifestDictType::Glossary),
+//! [`Ipa`](ManifestDictType::Ipa), [`FormOf`](ManifestDictType::FormOf),
+//! [`Hyphenation`](ManifestDictType::Hyphenation) and [`Forms`](ManifestDictType::Forms).
+//! `GlossaryExtended`, `IpaMerged` and `GlossaryMatrix` need more than one language pair and
+//! aren't supported here yet.
+//!
+//! The manifest file is JSON, not TOML: reading it with `serde_json` means no new parsing
+//! dependency for a crate that otherwise only ever serializes/deserializes JSON.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer};
+
+use crate::cli::{
+    ArgsOptions, Command, FormOfArgs, FormsArgs, GlossaryArgs, GlossaryLangs, HyphenationArgs,
+    IpaArgs, MainArgs, MainLangs, prepare_command,
+};
+use crate::dict::{DFormOf, DForms, DGlossary, DHyphenation, DIpa, DMain};
+use crate::lang::{EditionLang, Lang};
+use crate::make_dict;
+use crate::path::{DictionaryType, PathManager};
+
+/// One dictionary a manifest job can drive `make_dict` for.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestDictType {
+    Main,
+    Glossary,
+    Ipa,
+    FormOf,
+    Hyphenation,
+    Forms,
+}
+
+fn lang_from_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Lang, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+fn edition_lang_from_str<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<EditionLang, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+/// One dictionary to build. `edition` is always the edition reading/writing the dictionary and
+/// `lang` is always the other language in the pair; which role each plays (source or target)
+/// depends on `dict_type`, exactly as [`crate::cli::prepare_command`] resolves it for the
+/// equivalent CLI subcommand.
+#[derive(Debug, Deserialize)]
+pub struct ManifestJob {
+    /// Name used to match this job against the manifest's `only`/`except` selection, and used as
+    /// the dictionary name.
+    pub name: String,
+    pub dict_type: ManifestDictType,
+    #[serde(deserialize_with = "edition_lang_from_str")]
+    pub edition: EditionLang,
+    #[serde(deserialize_with = "lang_from_str")]
+    pub lang: Lang,
+    #[serde(default)]
+    pub options: ManifestOptionsOverride,
+}
+
+/// The common subset of [`ArgsOptions`] a manifest job is likely to want to override; anything
+/// else is inherited from the `build` command's own flags.
+#[derive(Debug, Default, Deserialize)]
+pub struct ManifestOptionsOverride {
+    pub quiet: Option<bool>,
+    pub save_temps: Option<bool>,
+    pub pretty: Option<bool>,
+    pub root_dir: Option<PathBuf>,
+}
+
+impl ManifestOptionsOverride {
+    fn apply(&self, mut options: ArgsOptions) -> ArgsOptions {
+        if let Some(quiet) = self.quiet {
+            options.quiet = quiet;
+        }
+        if let Some(save_temps) = self.save_temps {
+            options.save_temps = save_temps;
+        }
+        if let Some(pretty) = self.pretty {
+            options.pretty = pretty;
+        }
+        if let Some(root_dir) = &self.root_dir {
+            options.root_dir = root_dir.clone();
+        }
+        options
+    }
+}
+
+/// A build manifest: every dictionary to build, plus an optional curated selection.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    /// If non-empty, keep only jobs whose `name` is listed here.
+    #[serde(default)]
+    pub only: Vec<String>,
+    /// Drop jobs whose `name` is listed here, applied after `only`.
+    #[serde(default)]
+    pub except: Vec<String>,
+    pub jobs: Vec<ManifestJob>,
+}
+
+impl Manifest {
+    /// Read and parse a JSON manifest from `path`.
+    pub fn read(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading manifest {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("parsing manifest {}", path.display()))
+    }
+
+    /// Jobs left after applying `only` (if non-empty) then `except`, in manifest order.
+    pub fn resolved_jobs(&self) -> Vec<&ManifestJob> {
+        self.jobs
+            .iter()
+            .filter(|job| self.only.is_empty() || self.only.contains(&job.name))
+            .filter(|job| !self.except.contains(&job.name))
+            .collect()
+    }
+}
+
+/// A manifest job once its `*Args` have been expanded and run through
+/// [`crate::cli::prepare_command`], exactly as the CLI prepares the equivalent subcommand.
+enum PreparedJob {
+    Main(MainArgs),
+    Glossary(GlossaryArgs),
+    Ipa(IpaArgs),
+    FormOf(FormOfArgs),
+    Hyphenation(HyphenationArgs),
+    Forms(FormsArgs),
+}
+
+/// Expand `job` into its concrete `*Args` and validate it via `prepare_command`, so a bad job
+/// (e.g. a glossary whose source equals its target) is caught up front instead of mid-batch.
+fn prepare_job(job: &ManifestJob, options: ArgsOptions) -> Result<PreparedJob> {
+    macro_rules! prepare_main_like {
+        ($args_ty:ident, $variant:ident) => {{
+            let args = $args_ty {
+                langs: MainLangs {
+                    edition: job.edition,
+                    source: job.lang,
+                    target: job.edition,
+                },
+                dict_name: job.name.clone(),
+                options,
+            };
+            let mut cmd = Command::$variant(args);
+            prepare_command(&mut cmd)?;
+            let Command::$variant(args) = cmd else {
+                unreachable!("prepare_command does not change a command's variant")
+            };
+            Ok(PreparedJob::$variant(args))
+        }};
+    }
+
+    match job.dict_type {
+        ManifestDictType::Main => prepare_main_like!(MainArgs, Main),
+        ManifestDictType::Ipa => prepare_main_like!(IpaArgs, Ipa),
+        ManifestDictType::FormOf => prepare_main_like!(FormOfArgs, FormOf),
+        ManifestDictType::Hyphenation => prepare_main_like!(HyphenationArgs, Hyphenation),
+        ManifestDictType::Forms => prepare_main_like!(FormsArgs, Forms),
+        ManifestDictType::Glossary => {
+            let args = GlossaryArgs {
+                langs: GlossaryLangs {
+                    edition: job.edition,
+                    source: job.edition,
+                    target: job.lang,
+                },
+                dict_name: job.name.clone(),
+                options,
+            };
+            let mut cmd = Command::Glossary(args);
+            prepare_command(&mut cmd)?;
+            let Command::Glossary(args) = cmd else {
+                unreachable!("prepare_command does not change a command's variant")
+            };
+            Ok(PreparedJob::Glossary(args))
+        }
+    }
+}
+
+fn build_prepared(prepared: &PreparedJob) -> Result<()> {
+    macro_rules! build {
+        ($args:expr, $dict_ty:expr, $dict:expr) => {{
+            let pm = PathManager::new($dict_ty, $args);
+            make_dict($dict, &$args.options, &pm)
+        }};
+    }
+
+    match prepared {
+        PreparedJob::Main(args) => build!(args, DictionaryType::Main, DMain),
+        PreparedJob::Glossary(args) => build!(args, DictionaryType::Glossary, DGlossary),
+        PreparedJob::Ipa(args) => build!(args, DictionaryType::Ipa, DIpa),
+        PreparedJob::FormOf(args) => build!(args, DictionaryType::FormOf, DFormOf),
+        PreparedJob::Hyphenation(args) => {
+            build!(args, DictionaryType::Hyphenation, DHyphenation)
+        }
+        PreparedJob::Forms(args) => build!(args, DictionaryType::Forms, DForms),
+    }
+}
+
+/// Outcome of building one manifest job.
+pub struct JobReport {
+    pub name: String,
+    pub result: Result<()>,
+}
+
+/// Resolve `manifest`'s selection, validate every job up front, then build them concurrently on a
+/// thread pool sized to the available CPUs. Reports are returned in manifest order, not
+/// completion order.
+pub fn run(manifest: &Manifest, base_options: &ArgsOptions) -> Result<Vec<JobReport>> {
+    let prepared = manifest
+        .resolved_jobs()
+        .into_iter()
+        .map(|job| {
+            let options = job.options.apply(base_options.clone());
+            let prepared = prepare_job(job, options)
+                .with_context(|| format!("preparing manifest job '{}'", job.name))?;
+            Ok((job.name.clone(), prepared))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let num_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .context("building the batch build thread pool")?;
+
+    Ok(pool.install(|| {
+        prepared
+            .par_iter()
+            .map(|(name, prepared)| JobReport {
+                name: name.clone(),
+                result: build_prepared(prepared),
+            })
+            .collect()
+    }))
+}
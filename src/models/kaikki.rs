@@ -12,7 +12,8 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::tags::{BLACKLISTED_FORM_TAGS, IDENTITY_FORM_TAGS};
+use crate::lang::EditionLang;
+use crate::tags::is_blacklisted_or_identity_form;
 
 // In case we ever decide to narrow them
 pub type Tag = String;
@@ -32,6 +33,9 @@ pub struct WordEntry {
     etymology_text: String, // En, El editions still use this
     etymology_texts: Vec<String>,
 
+    // Structured etymology templates, as expanded by wiktextract. Not pub: use the getter.
+    etymology_templates: Vec<EtymologyTemplate>,
+
     pub sounds: Vec<Sound>,
 
     pub senses: Vec<Sense>,
@@ -53,6 +57,18 @@ pub struct HeadTemplate {
     pub expansion: String,
 }
 
+/// One expanded etymology template (`{{bor|en|fr|...}}`, `{{inh|...}}`, `{{cal|...}}`, …).
+///
+/// The `args` map is keyed by the template's positional/named parameters as strings
+/// ("1", "2", "3", ...), following wiktextract's own representation.
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct EtymologyTemplate {
+    pub name: String,
+    pub args: crate::Map<String, String>,
+    pub expansion: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, Default)]
 #[serde(default)]
 pub struct Sound {
@@ -108,6 +124,7 @@ pub struct Translation {
     pub lang_code: String,
     pub word: String,
     pub sense: String,
+    pub tags: Vec<Tag>,
 }
 
 // WordEntry impls
@@ -142,6 +159,15 @@ impl WordEntry {
         self.tagged_forms(&["transliteration"]).next()
     }
 
+    /// Return the first non-empty form tagged with the opposite-aspect counterpart verb, as
+    /// wiktextract exposes it on `{{ru-verb}}` headwords (e.g. `сде́лать` on the entry for
+    /// `де́лать`).
+    pub fn aspect_pair_form(&self) -> Option<&Form> {
+        self.tagged_forms(&["perfective"])
+            .next()
+            .or_else(|| self.tagged_forms(&["imperfective"]).next())
+    }
+
     /// Return the first `sound.zh_pron` with the `Pinyin` tag.
     pub fn pinyin(&self) -> Option<&str> {
         self.sounds.iter().find_map(|sound| {
@@ -164,9 +190,9 @@ impl WordEntry {
         }
     }
 
-    pub fn non_trivial_forms(&self) -> impl Iterator<Item = &Form> {
+    pub fn non_trivial_forms(&self, edition: EditionLang) -> impl Iterator<Item = &Form> {
         self.forms.iter().filter(move |form| {
-            if form.form == self.word {
+            if form.form.is_empty() || form.form == self.word {
                 return false;
             }
 
@@ -179,16 +205,8 @@ impl WordEntry {
                 return false;
             }
 
-            // blacklisted tags (happens at least in Russian: romanization)
-            let is_blacklisted = form
-                .tags
-                .iter()
-                .any(|tag| BLACKLISTED_FORM_TAGS.contains(&tag.as_str()));
-            let is_identity = form
-                .tags
-                .iter()
-                .all(|tag| IDENTITY_FORM_TAGS.contains(&tag.as_str()));
-            if is_blacklisted || is_identity {
+            // blacklisted/identity tags (happens at least in Russian: romanization)
+            if is_blacklisted_or_identity_form(&form.tags, edition) {
                 return false;
             }
 
@@ -211,4 +229,8 @@ impl WordEntry {
             None
         }
     }
+
+    pub fn etymology_templates(&self) -> &[EtymologyTemplate] {
+        &self.etymology_templates
+    }
 }
@@ -1,8 +1,13 @@
+use std::borrow::Cow;
+
 use crate::{Map, models::kaikki::Tag};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
 use serde::ser::{SerializeTuple, Serializer};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Clone)]
+// `TermBank` is tried before `TermBankMeta`: an untagged enum's derived `Deserialize` attempts
+// variants in declaration order, which mirrors `TermBank`'s manual Deserialize below.
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum YomitanEntry {
     TermBank(TermBank),         // 120 (24 * 5)
@@ -45,24 +50,80 @@ impl Serialize for TermBank {
     }
 }
 
-// There are other variants that we don't use at the moment.
-#[derive(Debug, Serialize, Clone)]
+impl<'de> Deserialize<'de> for TermBank {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TermBankVisitor;
+
+        impl<'de> Visitor<'de> for TermBankVisitor {
+            type Value = TermBank;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an 8-element term bank entry array")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let term = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let reading = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let definition_tags = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let rules = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                // frequency: may arrive as an integer or a float; we don't keep it.
+                seq.next_element::<de::IgnoredAny>()?
+                    .ok_or_else(|| de::Error::invalid_length(4, &self))?;
+                let definitions = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(5, &self))?;
+                // sequence
+                seq.next_element::<de::IgnoredAny>()?
+                    .ok_or_else(|| de::Error::invalid_length(6, &self))?;
+                // term_tags
+                seq.next_element::<de::IgnoredAny>()?
+                    .ok_or_else(|| de::Error::invalid_length(7, &self))?;
+
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(9, &self));
+                }
+
+                Ok(TermBank(term, reading, definition_tags, rules, definitions))
+            }
+        }
+
+        deserializer.deserialize_tuple(8, TermBankVisitor)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum TermBankMeta {
     TermPhoneticTranscription(TermPhoneticTranscription),
+    TermFrequency(TermFrequency),
+    TermPitchAccent(TermPitchAccent),
 }
 
 // https://github.com/yomidevs/yomitan/blob/f271fc0da3e55a98fa91c9834d75fccc96deae27/ext/data/schemas/dictionary-term-meta-bank-v3-schema.json
 //
 // https://github.com/MarvNC/yomichan-dict-builder/blob/master/src/types/yomitan/termbankmeta.ts
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TermPhoneticTranscription(
     pub String,                // term
     pub String,                // static: "ipa"
     pub PhoneticTranscription, // phonetic transcription
 );
 
-#[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct PhoneticTranscription {
     pub reading: String,
     pub transcriptions: Vec<Ipa>,
@@ -76,15 +137,66 @@ pub struct Ipa {
     pub tags: Vec<Tag>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TermFrequency(
+    pub String,         // term
+    pub String,         // static: "freq"
+    pub FrequencyValue, // frequency
+);
+
+/// A frequency-bank value: either a bare rank, a bare display string, a `{value, displayValue}`
+/// pair, or any of those scoped to a specific `reading` of the term.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum FrequencyValue {
+    Number(u64),
+    Text(String),
+    Detailed {
+        value: u64,
+        #[serde(rename = "displayValue")]
+        display_value: String,
+    },
+    Reading {
+        reading: String,
+        frequency: Box<FrequencyValue>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TermPitchAccent(
+    pub String,    // term
+    pub String,    // static: "pitch"
+    pub PitchData, // pitch data
+);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PitchData {
+    pub reading: String,
+    pub pitches: Vec<Pitch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Pitch {
+    pub position: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nasal: Option<Vec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devoice: Option<Vec<u8>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+}
+
 // https://github.com/MarvNC/yomichan-dict-builder/blob/master/src/types/yomitan/termbank.ts
 // @ StructuredContentNode
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum Node {
     Text(String),              // 32
     Array(Vec<Node>),          // 32
     Generic(Box<GenericNode>), // 16
     Backlink(BacklinkContent), // 40
+    Image(Box<ImageNode>),
 }
 
 impl Node {
@@ -106,7 +218,7 @@ impl Node {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NodeData(Map<String, String>);
 
 impl<K, V> FromIterator<(K, V)> for NodeData
@@ -123,7 +235,7 @@ where
     }
 }
 
-#[derive(Debug, Serialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum NTag {
     Span,
@@ -133,22 +245,66 @@ pub enum NTag {
     Li,
     Details,
     Summary,
+    Table,
+    Thead,
+    Tbody,
+    Tr,
+    Td,
+    Th,
+    Ruby,
+    Rt,
+    Rp,
 }
 
 // The order follows kty serialization, not yomichan builder order
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GenericNode {
     pub tag: NTag,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub data: Option<NodeData>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<ContentStyle>,
+
+    /// `td`/`th` only: how many columns this cell spans. A real integer attribute per the Yomitan
+    /// schema, not a `data` entry — `data` is free-form CSS-matching metadata, not cell layout.
+    #[serde(rename = "colSpan", default, skip_serializing_if = "Option::is_none")]
+    pub col_span: Option<u32>,
+
+    /// `td`/`th` only: how many rows this cell spans. See [`Self::col_span`].
+    #[serde(rename = "rowSpan", default, skip_serializing_if = "Option::is_none")]
+    pub row_span: Option<u32>,
+
     pub content: Node,
 }
 
+/// Inline styling for a structured-content node, mirroring Yomitan's `style` object. Every field
+/// is optional and serializes under the schema's camelCase name, omitted entirely when unset.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ContentStyle {
+    #[serde(rename = "fontStyle", skip_serializing_if = "Option::is_none")]
+    pub font_style: Option<String>,
+    #[serde(rename = "fontWeight", skip_serializing_if = "Option::is_none")]
+    pub font_weight: Option<String>,
+    #[serde(rename = "fontSize", skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(rename = "textDecorationLine", skip_serializing_if = "Option::is_none")]
+    pub text_decoration_line: Option<String>,
+    #[serde(rename = "verticalAlign", skip_serializing_if = "Option::is_none")]
+    pub vertical_align: Option<String>,
+    #[serde(rename = "marginTop", skip_serializing_if = "Option::is_none")]
+    pub margin_top: Option<f32>,
+    #[serde(rename = "listStyleType", skip_serializing_if = "Option::is_none")]
+    pub list_style_type: Option<String>,
+}
+
 impl GenericNode {
     pub fn into_node(self) -> Node {
         Node::Generic(Box::new(self))
@@ -158,14 +314,23 @@ impl GenericNode {
 #[derive(Debug, Clone)]
 pub struct BacklinkContent {
     href: String,
-    content: &'static str,
+    content: Cow<'static, str>,
 }
 
 impl BacklinkContent {
     pub fn new(href: &str, content: &'static str) -> Self {
         Self {
             href: href.to_string(),
-            content,
+            content: Cow::Borrowed(content),
+        }
+    }
+
+    /// Like [`BacklinkContent::new`] but with dynamically-built link text, used for
+    /// etymology cross-references where the content is the source term.
+    pub fn with_text(href: String, content: String) -> Self {
+        Self {
+            href,
+            content: Cow::Owned(content),
         }
     }
 }
@@ -185,9 +350,37 @@ impl Serialize for BacklinkContent {
     }
 }
 
+// Mirrors the Serialize impl above: read the constant 'a' tag back and reject anything else,
+// since a backlink node with a different tag isn't one we can round-trip through this type.
+impl<'de> Deserialize<'de> for BacklinkContent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Repr {
+            tag: String,
+            href: String,
+            content: String,
+        }
+
+        let repr = Repr::deserialize(deserializer)?;
+        if repr.tag != "a" {
+            return Err(de::Error::custom(format!(
+                "unexpected backlink tag '{}', expected 'a'",
+                repr.tag
+            )));
+        }
+        Ok(Self {
+            href: repr.href,
+            content: Cow::Owned(repr.content),
+        })
+    }
+}
+
 // https://github.com/MarvNC/yomichan-dict-builder/blob/master/src/types/yomitan/termbank.ts
 // @ DetailedDefinition
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum DetailedDefinition {
     Text(String),
@@ -204,7 +397,7 @@ impl DetailedDefinition {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StructuredContent {
     #[serde(rename = "type")]
     ty: String, // should be hardcoded to "structured-content" (but then to serialize it...)
@@ -219,11 +412,172 @@ pub fn wrap(tag: NTag, content_ty: &str, content: Node) -> Node {
             "" => None,
             _ => Some(NodeData::from_iter([("content", content_ty)])),
         },
+        style: None,
+        col_span: None,
+        row_span: None,
+        content,
+    }
+    .into_node()
+}
+
+/// Like [`wrap`], with an inline [`ContentStyle`] attached, for callers that need to emphasize a
+/// headword, grey out a gloss, or indent a nested list rather than just tag the node's content type.
+pub fn wrap_styled(tag: NTag, content_ty: &str, style: ContentStyle, content: Node) -> Node {
+    GenericNode {
+        tag,
+        title: None,
+        data: match content_ty {
+            "" => None,
+            _ => Some(NodeData::from_iter([("content", content_ty)])),
+        },
+        style: Some(style),
+        col_span: None,
+        row_span: None,
         content,
     }
     .into_node()
 }
 
+/// Like [`wrap`], for a `td`/`th` cell that spans more than one column/row. `col_span`/`row_span`
+/// are real `GenericNode` fields (see [`GenericNode::col_span`]), not `data` entries: the schema
+/// defines them as integer attributes on the cell itself, not free-form CSS-matching metadata.
+pub fn wrap_cell(tag: NTag, col_span: Option<u32>, row_span: Option<u32>, content: Node) -> Node {
+    GenericNode {
+        tag,
+        title: None,
+        data: None,
+        style: None,
+        col_span,
+        row_span,
+        content,
+    }
+    .into_node()
+}
+
+/// A Yomitan `img` structured-content node. Unlike [`GenericNode`] it has no `content` (an image
+/// isn't a container) and its attribute set is entirely its own, so it's a sibling [`Node`] variant
+/// rather than another `NTag`.
+#[derive(Debug, Clone)]
+pub struct ImageNode {
+    pub path: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub title: Option<String>,
+    pub alt: Option<String>,
+    pub collapsible: Option<bool>,
+    pub collapsed: Option<bool>,
+    pub background: Option<bool>,
+    pub appearance: Option<String>,
+}
+
+impl ImageNode {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            width: None,
+            height: None,
+            title: None,
+            alt: None,
+            collapsible: None,
+            collapsed: None,
+            background: None,
+            appearance: None,
+        }
+    }
+
+    #[must_use]
+    pub fn into_node(self) -> Node {
+        Node::Image(Box::new(self))
+    }
+}
+
+// Custom Serialize to not have to store the constant 'img' tag, and to only emit the attributes
+// that are actually set (mirrors BacklinkContent's approach for its constant 'a' tag).
+impl Serialize for ImageNode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("tag", "img")?;
+        map.serialize_entry("path", &self.path)?;
+        if let Some(width) = &self.width {
+            map.serialize_entry("width", width)?;
+        }
+        if let Some(height) = &self.height {
+            map.serialize_entry("height", height)?;
+        }
+        if let Some(title) = &self.title {
+            map.serialize_entry("title", title)?;
+        }
+        if let Some(alt) = &self.alt {
+            map.serialize_entry("alt", alt)?;
+        }
+        if let Some(collapsible) = &self.collapsible {
+            map.serialize_entry("collapsible", collapsible)?;
+        }
+        if let Some(collapsed) = &self.collapsed {
+            map.serialize_entry("collapsed", collapsed)?;
+        }
+        if let Some(background) = &self.background {
+            map.serialize_entry("background", background)?;
+        }
+        if let Some(appearance) = &self.appearance {
+            map.serialize_entry("appearance", appearance)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ImageNode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Repr {
+            tag: String,
+            path: String,
+            #[serde(default)]
+            width: Option<u32>,
+            #[serde(default)]
+            height: Option<u32>,
+            #[serde(default)]
+            title: Option<String>,
+            #[serde(default)]
+            alt: Option<String>,
+            #[serde(default)]
+            collapsible: Option<bool>,
+            #[serde(default)]
+            collapsed: Option<bool>,
+            #[serde(default)]
+            background: Option<bool>,
+            #[serde(default)]
+            appearance: Option<String>,
+        }
+
+        let repr = Repr::deserialize(deserializer)?;
+        if repr.tag != "img" {
+            return Err(de::Error::custom(format!(
+                "unexpected image tag '{}', expected 'img'",
+                repr.tag
+            )));
+        }
+        Ok(Self {
+            path: repr.path,
+            width: repr.width,
+            height: repr.height,
+            title: repr.title,
+            alt: repr.alt,
+            collapsible: repr.collapsible,
+            collapsed: repr.collapsed,
+            background: repr.background,
+            appearance: repr.appearance,
+        })
+    }
+}
+
 // Internal legacy types that are just for documentation since we ended up loading
 // tag_bank_term.json as a raw list of tuples in tags::mod.rs
 //
@@ -280,3 +634,286 @@ impl Serialize for TagInformation {
         tup.end()
     }
 }
+
+impl<'de> Deserialize<'de> for TagInformation {
+    // mirrors the Serialize impl above: a fixed 5-element array
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TagInformationVisitor;
+
+        impl<'de> Visitor<'de> for TagInformationVisitor {
+            type Value = TagInformation;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a 5-element tag bank entry array")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let short_tag = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let category = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let sort_order = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let long_tag = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                let popularity_score = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(4, &self))?;
+
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(6, &self));
+                }
+
+                Ok(TagInformation {
+                    short_tag,
+                    category,
+                    sort_order,
+                    long_tag,
+                    popularity_score,
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple(5, TagInformationVisitor)
+    }
+}
+
+/// Why one entry of a term/tag bank failed to validate, precise enough to point a user at the
+/// exact array position rather than just "this file didn't parse".
+///
+/// This is deliberately a thinner error than what [`serde::de::Error`] can express: it only has to
+/// describe the shallow tuple shape ([`TermBank`]/[`TagInformation`] are flat arrays), falling back
+/// to [`Self::UnexpectedValue`] for anything that fails deeper inside (e.g. a malformed
+/// [`DetailedDefinition`]).
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeError {
+    /// The element at `position` wasn't the JSON type the schema expects there.
+    WrongType {
+        position: usize,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The entry itself wasn't an array of `expected` elements.
+    WrongLength(usize, usize),
+    /// A required object field was absent.
+    MissingField { position: usize, field: String },
+    /// The element had a plausible JSON type but an invalid value (e.g. deeper inside a
+    /// structured-content node).
+    UnexpectedValue { position: usize, detail: String },
+}
+
+impl std::fmt::Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongType {
+                position,
+                expected,
+                found,
+            } => write!(f, "position {position}: expected {expected}, found {found}"),
+            Self::WrongLength(expected, got) => {
+                write!(f, "expected {expected} elements, found {got}")
+            }
+            Self::MissingField { position, field } => {
+                write!(f, "position {position}: missing required field '{field}'")
+            }
+            Self::UnexpectedValue { position, detail } => {
+                write!(f, "position {position}: unexpected value ({detail})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "bool",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+fn as_array(value: &serde_json::Value) -> Result<&Vec<serde_json::Value>, TypeError> {
+    match value {
+        serde_json::Value::Array(arr) => Ok(arr),
+        other => Err(TypeError::WrongType {
+            position: 0,
+            expected: "array",
+            found: json_type_name(other),
+        }),
+    }
+}
+
+fn string_at(arr: &[serde_json::Value], position: usize) -> Result<String, TypeError> {
+    match &arr[position] {
+        serde_json::Value::String(s) => Ok(s.clone()),
+        other => Err(TypeError::WrongType {
+            position,
+            expected: "string",
+            found: json_type_name(other),
+        }),
+    }
+}
+
+fn i32_at(arr: &[serde_json::Value], position: usize) -> Result<i32, TypeError> {
+    arr[position]
+        .as_i64()
+        .and_then(|n| i32::try_from(n).ok())
+        .ok_or_else(|| TypeError::WrongType {
+            position,
+            expected: "integer",
+            found: json_type_name(&arr[position]),
+        })
+}
+
+/// Best-effort translation of a `serde_json` parse failure (from decoding something nested, like a
+/// [`DetailedDefinition`]) into this module's [`TypeError`] vocabulary. `serde_json::Error` doesn't
+/// expose a structured reason, so this sniffs its message.
+fn classify_nested_error(position: usize, err: &serde_json::Error) -> TypeError {
+    let message = err.to_string();
+    if let Some(field) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+    {
+        return TypeError::MissingField {
+            position,
+            field: field.to_string(),
+        };
+    }
+    if message.starts_with("invalid type:") {
+        return TypeError::WrongType {
+            position,
+            expected: "a matching structured-content shape",
+            found: "a different JSON type",
+        };
+    }
+    TypeError::UnexpectedValue {
+        position,
+        detail: message,
+    }
+}
+
+/// Parse `reader` as a top-level JSON array of bank entries, without interpreting the entries
+/// themselves yet.
+fn parse_entry_array<R: std::io::Read>(
+    reader: R,
+) -> Result<Vec<serde_json::Value>, Vec<(usize, TypeError)>> {
+    let value: serde_json::Value = serde_json::from_reader(reader).map_err(|e| {
+        vec![(
+            0,
+            TypeError::UnexpectedValue {
+                position: 0,
+                detail: e.to_string(),
+            },
+        )]
+    })?;
+    match value {
+        serde_json::Value::Array(entries) => Ok(entries),
+        other => Err(vec![(
+            0,
+            TypeError::WrongType {
+                position: 0,
+                expected: "array",
+                found: json_type_name(&other),
+            },
+        )]),
+    }
+}
+
+fn validate_term_bank_entry(value: &serde_json::Value) -> Result<YomitanEntry, TypeError> {
+    let arr = as_array(value)?;
+    if arr.len() != 8 {
+        return Err(TypeError::WrongLength(8, arr.len()));
+    }
+
+    let term = string_at(arr, 0)?;
+    let reading = string_at(arr, 1)?;
+    let definition_tags = string_at(arr, 2)?;
+    let rules = string_at(arr, 3)?;
+    // Position 4 (frequency), 6 (sequence) and 7 (term_tags) are accepted but discarded, matching
+    // `TermBank`'s own `Deserialize` impl.
+    let definitions: Vec<DetailedDefinition> =
+        serde_json::from_value(arr[5].clone()).map_err(|e| classify_nested_error(5, &e))?;
+
+    Ok(YomitanEntry::TermBank(TermBank(
+        term,
+        reading,
+        definition_tags,
+        rules,
+        definitions,
+    )))
+}
+
+/// Parse and validate every entry of a `term_bank_*.json` file, collecting every entry's error
+/// (by index) instead of aborting on the first, so converting a large community dictionary
+/// produces one full report rather than a single opaque failure.
+pub fn validate_term_bank<R: std::io::Read>(
+    reader: R,
+) -> Result<Vec<YomitanEntry>, Vec<(usize, TypeError)>> {
+    let entries = parse_entry_array(reader)?;
+
+    let mut parsed = Vec::with_capacity(entries.len());
+    let mut errors = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        match validate_term_bank_entry(entry) {
+            Ok(entry) => parsed.push(entry),
+            Err(error) => errors.push((index, error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_tag_bank_entry(value: &serde_json::Value) -> Result<TagInformation, TypeError> {
+    let arr = as_array(value)?;
+    if arr.len() != 5 {
+        return Err(TypeError::WrongLength(5, arr.len()));
+    }
+
+    Ok(TagInformation {
+        short_tag: string_at(arr, 0)?,
+        category: string_at(arr, 1)?,
+        sort_order: i32_at(arr, 2)?,
+        long_tag: string_at(arr, 3)?,
+        popularity_score: i32_at(arr, 4)?,
+    })
+}
+
+/// Like [`validate_term_bank`], for a `tag_bank_*.json` file.
+pub fn validate_tag_bank<R: std::io::Read>(
+    reader: R,
+) -> Result<Vec<TagInformation>, Vec<(usize, TypeError)>> {
+    let entries = parse_entry_array(reader)?;
+
+    let mut parsed = Vec::with_capacity(entries.len());
+    let mut errors = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        match validate_tag_bank_entry(entry) {
+            Ok(entry) => parsed.push(entry),
+            Err(error) => errors.push((index, error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(errors)
+    }
+}
@@ -15,6 +15,10 @@ pub enum DictionaryType {
     GlossaryExtended,
     Ipa,
     IpaMerged,
+    FormOf,
+    GlossaryMatrix,
+    Hyphenation,
+    Forms,
 }
 
 /// Used only for the temporary files folder (`dir_temp`).
@@ -26,6 +30,10 @@ impl fmt::Display for DictionaryType {
             Self::GlossaryExtended => write!(f, "glossary-ext"),
             Self::Ipa => write!(f, "ipa"),
             Self::IpaMerged => write!(f, "ipa-merged"),
+            Self::FormOf => write!(f, "form-of"),
+            Self::GlossaryMatrix => write!(f, "glossary-matrix"),
+            Self::Hyphenation => write!(f, "hyphenation"),
+            Self::Forms => write!(f, "forms"),
         }
     }
 }
@@ -77,6 +85,16 @@ impl PathManager {
     pub fn dir_dicts(&self) -> PathBuf {
         self.root_dir.join("dict")
     }
+    /// Directory for the wiktextract databases.
+    ///
+    /// Example: `data/db`
+    pub fn dir_db(&self) -> PathBuf {
+        self.root_dir.join("db")
+    }
+    /// Example: `data/db/wiktextract_en.db`
+    pub fn path_db(&self, edition: Edition) -> PathBuf {
+        self.dir_db().join(format!("wiktextract_{edition}.db"))
+    }
     /// Example: `data/dict/el/el`
     fn dir_dict(&self) -> PathBuf {
         self.dir_dicts().join(match self.dict_ty {
@@ -171,12 +189,12 @@ impl PathManager {
                 .map(|edl| (edl, self.aliases(edl, edl.into())))
                 .collect(),
             // One edition, other_lang is used when filtering
-            Main | Ipa => {
+            Main | Ipa | FormOf => {
                 let edl = edition.try_into().unwrap();
                 vec![(edl, self.aliases(edl, source))]
             }
             // One edition, other_lang is not used when filtering
-            Glossary => {
+            Glossary | GlossaryMatrix => {
                 let edl = edition.try_into().unwrap();
                 vec![(edl, self.aliases(edl, edl.into()))]
             }
@@ -229,6 +247,12 @@ impl PathManager {
                 format!("{}-{}-{}-ipa", self.dict_name, self.source, self.target)
             }
             DictionaryType::IpaMerged => format!("{}-{}-ipa", self.dict_name, self.target),
+            DictionaryType::FormOf => {
+                format!("{}-{}-{}-form", self.dict_name, self.source, self.target)
+            }
+            DictionaryType::GlossaryMatrix => {
+                format!("{}-{}-matrix-gloss", self.dict_name, self.edition)
+            }
         };
 
         if self.experimental {
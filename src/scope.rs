@@ -0,0 +1,325 @@
+//! Usage-register (scope) classification for senses.
+//!
+//! Wiktionary marks many senses with register labels such as `archaic` or `slang`. Editions spell
+//! these differently (and sometimes park them in `topics` instead of `tags`), so we normalize the
+//! raw strings onto a small [`Scope`] enum. A build can then exclude whole registers to produce a
+//! leaner "common-only" dictionary, similar to jmdict's `scope-uncommon`/`scope-archaic`.
+
+use std::str::FromStr;
+
+use crate::lang::Lang;
+use crate::models::kaikki::{Sense, Tag, Translation, WordEntry};
+
+/// A coarse usage register a sense can belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Archaic,
+    Obsolete,
+    Rare,
+    Dated,
+    Dialectal,
+    Colloquial,
+    Slang,
+}
+
+impl Scope {
+    /// Map a single raw `Sense.tags`/`Sense.topics` string onto a [`Scope`], if it marks one.
+    ///
+    /// The table is deliberately small: near-synonyms across editions collapse onto the same
+    /// variant (e.g. `informal` is treated as `colloquial`, `nonstandard` as `dialectal`).
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let scope = match tag {
+            "archaic" => Self::Archaic,
+            "obsolete" | "historical" => Self::Obsolete,
+            "rare" | "uncommon" => Self::Rare,
+            "dated" => Self::Dated,
+            "dialectal" | "regional" | "nonstandard" => Self::Dialectal,
+            "colloquial" | "informal" | "familiar" => Self::Colloquial,
+            "slang" | "vulgar" => Self::Slang,
+            _ => return None,
+        };
+        Some(scope)
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Archaic => "archaic",
+            Self::Obsolete => "obsolete",
+            Self::Rare => "rare",
+            Self::Dated => "dated",
+            Self::Dialectal => "dialectal",
+            Self::Colloquial => "colloquial",
+            Self::Slang => "slang",
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_tag(s).ok_or_else(|| {
+            format!(
+                "unknown scope '{s}'. Choose between: \
+                 archaic | obsolete | rare | dated | dialectal | colloquial | slang"
+            )
+        })
+    }
+}
+
+/// A coarse, entry-level register used as a size/quality knob across every dictionary kind.
+///
+/// Where [`Scope`] marks one sense, a `Register` classifies a whole [`WordEntry`]: a headword is
+/// only as mainstream as its least-marked sense, so an unmarked or common sense keeps the entry
+/// [`Common`](Register::Common). Variants are ordered most- to least-mainstream so `--include-scopes`
+/// / `--exclude-scopes` read as ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Register {
+    Common,
+    Uncommon,
+    Rare,
+    Archaic,
+    Obsolete,
+    Dialectal,
+}
+
+impl Register {
+    /// The register implied by a single sense-level [`Scope`].
+    const fn from_scope(scope: Scope) -> Self {
+        match scope {
+            Scope::Rare => Self::Rare,
+            Scope::Archaic => Self::Archaic,
+            Scope::Obsolete => Self::Obsolete,
+            Scope::Dialectal => Self::Dialectal,
+            // Stylistic-but-current labels don't make a word rare, only less neutral.
+            Scope::Dated | Scope::Colloquial | Scope::Slang => Self::Uncommon,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Common => "common",
+            Self::Uncommon => "uncommon",
+            Self::Rare => "rare",
+            Self::Archaic => "archaic",
+            Self::Obsolete => "obsolete",
+            Self::Dialectal => "dialectal",
+        }
+    }
+}
+
+impl FromStr for Register {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let register = match s {
+            "common" => Self::Common,
+            "uncommon" => Self::Uncommon,
+            "rare" => Self::Rare,
+            "archaic" => Self::Archaic,
+            "obsolete" => Self::Obsolete,
+            "dialectal" => Self::Dialectal,
+            _ => {
+                return Err(format!(
+                    "unknown scope '{s}'. Choose between: \
+                     common | uncommon | rare | archaic | obsolete | dialectal"
+                ));
+            }
+        };
+        Ok(register)
+    }
+}
+
+impl std::fmt::Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The register a single sense belongs to, `Common` when it carries no recognized marker.
+fn sense_register(sense: &Sense) -> Register {
+    sense
+        .tags
+        .iter()
+        .chain(&sense.topics)
+        .filter_map(|tag| Scope::from_tag(tag))
+        .map(Register::from_scope)
+        .min()
+        .unwrap_or(Register::Common)
+}
+
+/// Every raw tag/topic string attached anywhere in `entry`: its own `tags`/`topics` plus every
+/// sense's. Used by `--filter tag,...`/`--reject tag,...`/`--scope`, which (unlike `--filter
+/// pos,...`/`--filter word,...`) match against a whole collection rather than a single field.
+fn entry_tags(entry: &WordEntry) -> impl Iterator<Item = &str> {
+    entry
+        .tags
+        .iter()
+        .chain(&entry.topics)
+        .chain(
+            entry
+                .senses
+                .iter()
+                .flat_map(|sense| sense.tags.iter().chain(&sense.topics)),
+        )
+        .map(String::as_str)
+}
+
+/// Whether `entry` carries the literal tag/topic `value` anywhere, for `--filter tag,<value>` and
+/// `--reject tag,<value>`.
+pub fn entry_has_tag(entry: &WordEntry, value: &str) -> bool {
+    entry_tags(entry).any(|tag| tag == value)
+}
+
+/// Whether `entry` carries a tag/topic matching `pattern` anywhere, for `--filter tag,~<pattern>`
+/// and `--reject tag,~<pattern>`.
+pub fn entry_has_tag_matching(entry: &WordEntry, pattern: &regex::Regex) -> bool {
+    entry_tags(entry).any(|tag| pattern.is_match(tag))
+}
+
+/// Whether `entry` carries a tag/topic that normalizes to any of `scopes`, for `--scope`.
+pub fn entry_has_any_scope(entry: &WordEntry, scopes: &[Scope]) -> bool {
+    entry_tags(entry).any(|tag| Scope::from_tag(tag).is_some_and(|scope| scopes.contains(&scope)))
+}
+
+/// Classify a whole entry by its least-marked sense.
+///
+/// A headword with any mainstream sense stays [`Register::Common`]; only entries whose every sense
+/// is marked take a narrower register. This mirrors the "kept unless fully excluded" rule of the
+/// sense-level [`WordEntry::retain_senses_in_scope`] filter, one level up.
+pub fn classify(entry: &WordEntry, _source: Lang) -> Register {
+    entry
+        .senses
+        .iter()
+        .map(sense_register)
+        .min()
+        .unwrap_or(Register::Common)
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Whether every tag in `tags` marks an excluded scope, in which case the item can be dropped.
+///
+/// An item with no recognized register is always kept: we only drop items we can positively
+/// classify as belonging entirely to excluded scopes. A tag that marks no scope (e.g. `figurative`)
+/// keeps the item too. Shared by the sense and translation filters.
+fn is_excluded<'a>(tags: impl Iterator<Item = &'a Tag>, excluded: &[Scope]) -> bool {
+    let mut tags = tags.peekable();
+    tags.peek().is_some()
+        && tags.all(|tag| Scope::from_tag(tag).is_some_and(|scope| excluded.contains(&scope)))
+}
+
+/// Whether every tag and topic of a sense marks an excluded scope.
+fn sense_is_excluded(sense: &Sense, excluded: &[Scope]) -> bool {
+    is_excluded(sense.tags.iter().chain(&sense.topics), excluded)
+}
+
+/// Whether every tag of a translation marks an excluded scope.
+fn translation_is_excluded(translation: &Translation, excluded: &[Scope]) -> bool {
+    is_excluded(translation.tags.iter(), excluded)
+}
+
+impl WordEntry {
+    /// Drop senses that fall entirely within `excluded` scopes.
+    ///
+    /// Returns `true` if the entry still carries a gloss afterwards (cf. [`WordEntry::contains_no_gloss`]);
+    /// a `false` return means the caller should skip the entry entirely.
+    pub fn retain_senses_in_scope(&mut self, excluded: &[Scope]) -> bool {
+        if !excluded.is_empty() {
+            self.senses
+                .retain(|sense| !sense_is_excluded(sense, excluded));
+        }
+        !self.contains_no_gloss()
+    }
+
+    /// Drop translations that fall entirely within `excluded` scopes.
+    ///
+    /// The glossary dictionaries are built from `translations` rather than senses, so they apply
+    /// this filter (in `preprocess`) to trim archaic/rare/etc. translations before the entry is
+    /// turned into term banks. As with [`WordEntry::retain_senses_in_scope`], a translation with no
+    /// recognized register is always kept.
+    pub fn retain_translations_in_scope(&mut self, excluded: &[Scope]) {
+        if !excluded.is_empty() {
+            self.translations
+                .retain(|translation| !translation_is_excluded(translation, excluded));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sense(tags: &[&str]) -> Sense {
+        Sense {
+            glosses: vec!["a gloss".to_string()],
+            tags: tags.iter().map(|t| (*t).to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn normalizes_synonyms() {
+        assert_eq!(Scope::from_tag("informal"), Some(Scope::Colloquial));
+        assert_eq!(Scope::from_tag("uncommon"), Some(Scope::Rare));
+        assert_eq!(Scope::from_tag("common"), None);
+    }
+
+    #[test]
+    fn retains_unclassified_and_mixed_senses() {
+        let excluded = [Scope::Archaic, Scope::Slang];
+
+        let mut entry = WordEntry::default();
+        entry.senses = vec![
+            sense(&["archaic"]),             // fully excluded -> dropped
+            sense(&["slang", "figurative"]), // mixed -> kept
+            sense(&[]),                      // unclassified -> kept
+        ];
+
+        assert!(entry.retain_senses_in_scope(&excluded));
+        assert_eq!(entry.senses.len(), 2);
+    }
+
+    #[test]
+    fn retains_unclassified_and_mixed_translations() {
+        let translation = |tags: &[&str]| Translation {
+            word: "w".to_string(),
+            tags: tags.iter().map(|t| (*t).to_string()).collect(),
+            ..Default::default()
+        };
+
+        let mut entry = WordEntry::default();
+        entry.translations = vec![
+            translation(&["archaic"]),        // fully excluded -> dropped
+            translation(&["rare", "poetic"]), // mixed -> kept
+            translation(&[]),                 // unclassified -> kept
+        ];
+
+        entry.retain_translations_in_scope(&[Scope::Archaic, Scope::Rare]);
+        assert_eq!(entry.translations.len(), 2);
+    }
+
+    #[test]
+    fn classifies_entry_by_least_marked_sense() {
+        let mut entry = WordEntry::default();
+        // A mainstream sense keeps the whole entry common despite an archaic one.
+        entry.senses = vec![sense(&["archaic"]), sense(&[])];
+        assert_eq!(classify(&entry, Lang::En), Register::Common);
+
+        // Every sense marked -> the entry takes its narrowest (most mainstream) marker.
+        entry.senses = vec![sense(&["obsolete"]), sense(&["rare"])];
+        assert_eq!(classify(&entry, Lang::En), Register::Rare);
+    }
+
+    #[test]
+    fn skips_entry_with_no_surviving_gloss() {
+        let mut entry = WordEntry::default();
+        entry.senses = vec![sense(&["obsolete"])];
+
+        assert!(!entry.retain_senses_in_scope(&[Scope::Obsolete]));
+    }
+}
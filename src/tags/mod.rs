@@ -5,13 +5,14 @@ use std::cmp::Ordering;
 use indexmap::IndexMap;
 use tags_constants::{POSES, TAG_BANK, TAG_ORDER};
 
+use crate::lang::EditionLang;
 use crate::models::kaikki::Tag;
 use crate::models::yomitan::TagInformation;
 
 // TODO: a bunch of sorting and handling of tags should go here
 
-/// Tags that are blacklisted if they happen at *some* expanded form @ tidy
-pub const BLACKLISTED_FORM_TAGS: [&str; 14] = [
+/// Tags that are blacklisted at *every* edition if they happen at some expanded form @ tidy.
+const BLACKLISTED_FORM_TAGS: [&str; 13] = [
     "inflection-template",
     "table-tags",
     "canonical",
@@ -25,21 +26,11 @@ pub const BLACKLISTED_FORM_TAGS: [&str; 14] = [
     "romanization",
     "dated",
     "auxiliary",
-    // multiword-construction was in REDUNDANT_TAGS in the original.
-    // Yet it only seems to give noise for the fr-en edition (@ prendre):
-    // * Form: 'present indicative of avoir + past participle' ???
-    // * Tags: ["indicative", "multiword-construction", "perfect", "present"]
-    //
-    // It also removes valid german forms that are nonetheless most useless:
-    // * werde gepflogen haben (for pflegen)
-    // (note that gepflogen is already added)
-    // This was considered ok. To revisit if it is more intrusive in other languages.
-    "multiword-construction",
 ];
 /// Tags that are blacklisted if they happen at *every* expanded form @ tidy
-pub const IDENTITY_FORM_TAGS: [&str; 3] = ["nominative", "singular", "infinitive"];
+const IDENTITY_FORM_TAGS: [&str; 3] = ["nominative", "singular", "infinitive"];
 /// Tags that we just remove from forms @ tidy
-pub const REDUNDANT_FORM_TAGS: [&str; 1] = ["combined-form"];
+const REDUNDANT_FORM_TAGS: [&str; 1] = ["combined-form"];
 
 /// Sort tags by their position in the tag bank.
 ///
@@ -102,14 +93,33 @@ pub fn remove_redundant_tags(tags: &mut Vec<Tag>) {
         a_words == b_words
     });
 
-    let mut keep = vec![true; tags.len()];
+    // Canonicalize each tag to a sorted word set so containment is a set operation.
+    let sets: Vec<Vec<&str>> = tags
+        .iter()
+        .map(|tag| {
+            let mut words: Vec<&str> = tag.split(' ').collect();
+            words.sort_unstable();
+            words
+        })
+        .collect();
 
-    for i in 0..tags.len() {
-        for j in 0..tags.len() {
-            // tag_i <= tag_j
-            if i != j && tags_are_subset(&tags[i], &tags[j]) {
-                keep[i] = false;
-                break;
+    // Visit largest word sets first so every superset is already kept by the time we test a
+    // smaller set against it. `sort_by` is stable, so equal-size sets keep their input order
+    // (of those, only identical word sets, which the adjacent-only dedup above can leave behind,
+    // contain one another; the first of them is kept).
+    let mut order: Vec<usize> = (0..tags.len()).collect();
+    order.sort_by(|&a, &b| sets[b].len().cmp(&sets[a].len()));
+
+    // Index kept sets under each of their words; a candidate can only be contained in a kept set
+    // that shares all of its words, so we probe the postings of its rarest word.
+    let mut postings: std::collections::HashMap<&str, Vec<usize>> = std::collections::HashMap::new();
+    let mut keep = vec![false; tags.len()];
+
+    for &i in &order {
+        if !is_contained_in_kept(&sets[i], &sets, &postings) {
+            keep[i] = true;
+            for &word in &sets[i] {
+                postings.entry(word).or_default().push(i);
             }
         }
     }
@@ -122,33 +132,144 @@ pub fn remove_redundant_tags(tags: &mut Vec<Tag>) {
     });
 }
 
-/// Check if all words in string `a` are present in string `b`.
+/// Whether the sorted word set `set` is contained in some already-kept set.
 ///
-/// F.e. "foo bar" is subset of "bar foo baz"
-fn tags_are_subset(a: &str, b: &str) -> bool {
-    a.split(' ')
-        .all(|a_word| b.split(' ').any(|b_word| b_word == a_word))
+/// Only kept sets sharing all of `set`'s words can contain it, so we probe the postings of the
+/// candidate's rarest word and test that short list rather than every kept set.
+fn is_contained_in_kept(
+    set: &[&str],
+    sets: &[Vec<&str>],
+    postings: &std::collections::HashMap<&str, Vec<usize>>,
+) -> bool {
+    let Some(rarest) = set
+        .iter()
+        .map(|word| postings.get(word).map_or(0, Vec::len))
+        .enumerate()
+        .min_by_key(|&(_, len)| len)
+        .map(|(i, _)| set[i])
+    else {
+        return false;
+    };
+
+    let Some(candidates) = postings.get(rarest) else {
+        return false;
+    };
+
+    candidates
+        .iter()
+        .any(|&j| set.iter().all(|word| sets[j].contains(word)))
 }
 
 const PERSON_TAGS: [&str; 3] = ["first-person", "second-person", "third-person"];
 
-fn person_sort(tags: &mut [&str]) {
-    tags.sort_by_key(|x| PERSON_TAGS.iter().position(|p| p == x).unwrap_or(999));
+/// A grammatical axis along which near-duplicate tags can be folded into one combined label.
+///
+/// Each axis names the mutually-exclusive tags that vary along it (its [`members`]) in canonical
+/// order. [`merge_tags_by_axes`] collapses forms that differ only by the axis member into a single
+/// slashed label (e.g. the [`Person`] axis turns `first-person`/`third-person` into
+/// `first/third-person`), sharing a common suffix when the members have one.
+///
+/// [`members`]: MergeAxis::members
+/// [`Person`]: MergeAxis::Person
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeAxis {
+    /// `first-person` / `second-person` / `third-person`.
+    Person,
+    /// `singular` / `dual` / `plural`.
+    Number,
+    /// `masculine` / `feminine` / `neuter`.
+    Gender,
+    /// `nominative` / `accusative` / `genitive` / `dative` / `instrumental` / `locative`.
+    Case,
+    /// `present` / `past` / `future` / `imperfect` / `preterite`.
+    Tense,
 }
 
-/// Merge similar tags if the only difference is the person-tags.
+impl MergeAxis {
+    /// The mutually-exclusive tags that vary along this axis, in canonical order.
+    fn members(self) -> &'static [&'static str] {
+        match self {
+            Self::Person => &PERSON_TAGS,
+            Self::Number => &["singular", "dual", "plural"],
+            Self::Gender => &["masculine", "feminine", "neuter"],
+            Self::Case => &[
+                "nominative",
+                "accusative",
+                "genitive",
+                "dative",
+                "instrumental",
+                "locative",
+            ],
+            Self::Tense => &["present", "past", "future", "imperfect", "preterite"],
+        }
+    }
+
+    /// Sort matched members into canonical axis order.
+    fn sort_matched(self, matched: &mut [&str]) {
+        let members = self.members();
+        matched.sort_by_key(|x| members.iter().position(|m| m == x).unwrap_or(usize::MAX));
+    }
+
+    /// Fold matched members into a single label, sharing their common `-`-delimited suffix.
+    ///
+    /// F.e. `[first-person, third-person] -> first/third-person`, while members without a shared
+    /// suffix (e.g. `singular`/`plural`) are simply slashed: `singular/plural`.
+    fn merged_label(self, matched: &[&str]) -> String {
+        let suffix = common_dash_suffix(matched);
+        matched
+            .iter()
+            .map(|m| m.strip_suffix(suffix).unwrap_or(m))
+            .collect::<Vec<_>>()
+            .join("/")
+            + suffix
+    }
+}
+
+/// Longest suffix shared by every string in `items` that begins at a `-` boundary (or `""`).
+fn common_dash_suffix<'a>(items: &[&'a str]) -> &'a str {
+    let Some(first) = items.first() else {
+        return "";
+    };
+    // Longest common suffix (in bytes; tag words are ASCII).
+    let mut len = first.len();
+    for item in &items[1..] {
+        let shared = first
+            .bytes()
+            .rev()
+            .zip(item.bytes().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        len = len.min(shared);
+    }
+    let candidate = &first[first.len() - len..];
+    // Trim forward to the first '-' so the suffix starts on a word boundary.
+    match candidate.find('-') {
+        Some(pos) => &candidate[pos..],
+        None => "",
+    }
+}
+
+/// Merge similar tags that differ only along one of `axes`, folding each axis in priority order.
 ///
-/// F.e.
-/// in:  ['first-person singular', 'third-person singular']
-/// out: ['singular first/third-person ']
+/// For every axis, forms carrying exactly one of that axis's members are grouped by their
+/// remaining tags and the members folded into a single slashed label (see
+/// [`MergeAxis::merged_label`]). Folding one axis can feed the next, so callers list axes from
+/// most to least significant.
 ///
-/// Note that this does not preserve logical tag order, and should be called before sort_tag.
-pub fn merge_person_tags(tags: &mut Vec<Tag>) {
-    let contains_person = tags
+/// Note that this does not preserve logical tag order, and should be called before `sort_tags`.
+pub fn merge_tags_by_axes(tags: &mut Vec<Tag>, axes: &[MergeAxis]) {
+    for &axis in axes {
+        fold_axis(tags, axis);
+    }
+}
+
+fn fold_axis(tags: &mut Vec<Tag>, axis: MergeAxis) {
+    let members = axis.members();
+    let contains_axis = tags
         .iter()
-        .any(|tag| PERSON_TAGS.iter().any(|p| tag.contains(p)));
+        .any(|tag| tag.split(' ').any(|w| members.contains(&w)));
 
-    if !contains_person {
+    if !contains_axis {
         return;
     }
 
@@ -156,37 +277,469 @@ pub fn merge_person_tags(tags: &mut Vec<Tag>) {
     let mut grouped: IndexMap<Vec<&str>, Vec<&str>> = IndexMap::new();
 
     for tag in &unmerged_tags {
-        let (person_tags, other_tags): (Vec<_>, Vec<_>) =
-            tag.split(' ').partition(|t| PERSON_TAGS.contains(t));
+        let (axis_tags, other_tags): (Vec<_>, Vec<_>) =
+            tag.split(' ').partition(|t| members.contains(t));
 
-        match person_tags.as_slice() {
-            [person] => grouped.entry(other_tags).or_default().push(person),
+        match axis_tags.as_slice() {
+            [one] => grouped.entry(other_tags).or_default().push(one),
             _ => tags.push(tag.to_string()),
         }
     }
 
-    for (other_tags, mut person_matches) in grouped {
+    for (other_tags, mut matched) in grouped {
         let mut tags_cur: Vec<_> = other_tags.iter().map(|s| s.to_string()).collect();
 
-        person_sort(&mut person_matches);
-
-        // [first-person, third-person] > first/third-person
-        let merged_tag = format!(
-            "{}-person",
-            person_matches
-                .iter()
-                // SAFETY: PERSON_TAGS contains pmatch so it always ends in -person
-                .map(|pmatch| pmatch.strip_suffix("-person").unwrap())
-                .collect::<Vec<_>>() // unlucky collect because we can't join a map
-                .join("/")
-        );
+        axis.sort_matched(&mut matched);
+        tags_cur.push(axis.merged_label(&matched));
 
-        tags_cur.push(merged_tag);
-        // sort_tags(&mut tags_cur);
         tags.push(tags_cur.join(" "));
     }
 }
 
+/// Merge similar tags if the only difference is the person-tags.
+///
+/// F.e.
+/// in:  ['first-person singular', 'third-person singular']
+/// out: ['singular first/third-person']
+///
+/// Note that this does not preserve logical tag order, and should be called before `sort_tags`.
+///
+/// This is the [`MergeAxis::Person`] instance of the general [`merge_tags_by_axes`] engine.
+pub fn merge_person_tags(tags: &mut Vec<Tag>) {
+    merge_tags_by_axes(tags, &[MergeAxis::Person]);
+}
+
+/// Per-edition grammeme normalization table: maps a raw source tag onto the canonical tag(s) used
+/// downstream. The mapping is one-to-many, so a compact edition code such as `"f_acc"` can expand
+/// to `["feminine", "accusative"]` before the dedup/merge/sort pipeline runs.
+///
+/// Each edition that needs remapping returns its own table here; editions already spelling tags
+/// the canonical way return an empty slice and [`normalize_tags`] is a no-op for them.
+fn grammeme_table(edition: EditionLang) -> &'static [(&'static str, &'static [&'static str])] {
+    match edition {
+        // Russian compact case/gender codes seen in inflection tables.
+        EditionLang::Ru => &[
+            ("m_nom", &["masculine", "nominative"]),
+            ("f_nom", &["feminine", "nominative"]),
+            ("n_nom", &["neuter", "nominative"]),
+            ("m_acc", &["masculine", "accusative"]),
+            ("f_acc", &["feminine", "accusative"]),
+            ("n_acc", &["neuter", "accusative"]),
+        ],
+        _ => &[],
+    }
+}
+
+/// Normalize raw `tags` for `edition` onto the canonical tag vocabulary, expanding one-to-many
+/// where the edition uses compact grammeme codes.
+///
+/// This should run before [`remove_redundant_tags`]/[`merge_person_tags`]/[`sort_tags`] so the
+/// rest of the pipeline only ever sees canonical tags. Order is preserved; unmapped tags pass
+/// through untouched.
+pub fn normalize_tags(edition: EditionLang, tags: &mut Vec<Tag>) {
+    let table = grammeme_table(edition);
+    if table.is_empty() {
+        return;
+    }
+
+    let raw = std::mem::take(tags);
+    for tag in raw {
+        match table.iter().find(|(src, _)| *src == tag) {
+            Some((_, canonical)) => tags.extend(canonical.iter().map(|c| (*c).to_string())),
+            None => tags.push(tag),
+        }
+    }
+}
+
+/// Canonical usage/register-label registry.
+///
+/// Maps the many spellings Wiktionary editions use for the same usage label onto a single
+/// canonical form, mirroring the label vocabulary used to author Russian entries. This folds
+/// synonyms so that semantically identical labels (e.g. `figuratively` / `fig` / `figurative`)
+/// arrive consistently across editions without per-edition `match` blocks downstream.
+///
+/// Each entry is `(canonical, &[synonyms])`; the canonical form is always accepted as its own
+/// synonym. Tags not listed here are left untouched.
+const USAGE_LABELS: &[(&str, &[&str])] = &[
+    ("figurative", &["figuratively", "fig"]),
+    ("historical", &["historically"]),
+    ("colloquial", &["colloquially", "informal"]),
+    ("low-colloquial", &["low colloquial", "vulgar"]),
+    ("dated", &["old-fashioned"]),
+    ("literary", &["bookish"]),
+    ("poetic", &["poetically"]),
+    ("transitive", &[]),
+    ("intransitive", &[]),
+    ("imperfective-only", &["imperfective only"]),
+    ("perfective-only", &["perfective only"]),
+    ("impersonal", &[]),
+    ("animate", &[]),
+    ("inanimate", &[]),
+];
+
+/// The canonical usage label `tag` folds to, if it is a known usage/register label.
+pub fn canonical_usage_label(tag: &str) -> Option<&'static str> {
+    USAGE_LABELS.iter().find_map(|(canonical, synonyms)| {
+        (*canonical == tag || synonyms.contains(&tag)).then_some(*canonical)
+    })
+}
+
+/// Fold every recognized usage/register label in `tags` onto its canonical form in place.
+///
+/// Unrecognized tags pass through untouched; order is preserved.
+pub fn canonicalize_usage_labels(tags: &mut [Tag]) {
+    for tag in tags.iter_mut() {
+        if let Some(canonical) = canonical_usage_label(tag) {
+            *tag = canonical.to_string();
+        }
+    }
+}
+
+/// A single declarative tag-rewrite rule, loosely modelled on MRS transfer rules.
+///
+/// A rule fires on a tag set when every tag in `context` and in `input` is present and none of the
+/// tags in `filter` are, in which case the tags in `input` are removed and the tags in `output`
+/// added. `context`/`filter` only gate the rewrite; `input` must be present and is what gets consumed.
+///
+/// This lets per-language tidy-ups (the `multiword-construction` fr-en vs. de caveats that used to
+/// live as comments next to [`BLACKLISTED_FORM_TAGS`]) be expressed as data instead of code.
+///
+/// [`merge_person_tags`] does not fit this shape and stays separate: a rule rewrites one form's
+/// flat tag set in place, while person-folding groups *several* forms by their shared remainder
+/// tags and only then folds the differing member in, which needs the grouping `merge_tags_by_axes`
+/// does, not a context/filter/input/output match on a single set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagRule {
+    /// Tags that must all be present for the rule to fire.
+    pub context: Vec<Tag>,
+    /// Tags that must all be absent for the rule to fire.
+    pub filter: Vec<Tag>,
+    /// Tags consumed (removed) when the rule fires.
+    pub input: Vec<Tag>,
+    /// Tags produced (added) when the rule fires.
+    pub output: Vec<Tag>,
+    /// Carried with the rule only: [`apply_tag_rules`] applies optional and non-optional rules
+    /// alike and reports no fixpoint status.
+    pub optional: bool,
+}
+
+impl TagRule {
+    /// Whether `context` matches and `filter` does not on `tags`, i.e. the rule is eligible.
+    fn eligible(&self, tags: &[Tag]) -> bool {
+        self.context.iter().all(|t| tags.contains(t))
+            && !self.filter.iter().any(|t| tags.contains(t))
+            && self.input.iter().all(|t| tags.contains(t))
+    }
+
+    /// Apply the rewrite in place, returning whether anything changed.
+    fn apply(&self, tags: &mut Vec<Tag>) -> bool {
+        if !self.eligible(tags) {
+            return false;
+        }
+        tags.retain(|t| !self.input.contains(t));
+        let mut changed = !self.input.is_empty();
+        for out in &self.output {
+            if !tags.contains(out) {
+                tags.push(out.clone());
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// `REDUNDANT_FORM_TAGS` as unconditional removal rules, one per tag: no `context`/`filter` gate,
+/// `input` is the single tag, `output` is empty.
+fn redundant_form_tag_rules() -> Vec<TagRule> {
+    REDUNDANT_FORM_TAGS
+        .iter()
+        .map(|tag| TagRule {
+            input: vec![(*tag).to_string()],
+            ..TagRule::default()
+        })
+        .collect()
+}
+
+/// Strip every tag in [`REDUNDANT_FORM_TAGS`] from `tags`, via [`apply_tag_rules`].
+pub fn strip_redundant_form_tags(tags: &mut Vec<Tag>) {
+    apply_tag_rules(&redundant_form_tag_rules(), tags);
+}
+
+/// `BLACKLISTED_FORM_TAGS` as context-only rules, one per tag, with no `input`/`output`: there is
+/// nothing to rewrite, only to query via [`TagRule::eligible`], so [`is_blacklisted_or_identity_form`]
+/// never has to run the fixpoint engine or clone the caller's tags. `multiword-construction` is the
+/// per-language tidy-up this engine was built for: it's noise for fr-en (e.g. "present indicative
+/// of avoir + past participle") but legitimate for German ("werde gepflogen haben" for "pflegen"),
+/// so its rule is only included for editions other than [`EditionLang::De`].
+///
+/// Built once per edition bucket and cached, since this is checked once per form across an entire
+/// dictionary build (the same `OnceLock` pattern as `download_lock_for`/`hyphenation_dict`
+/// elsewhere in this crate).
+fn blacklisted_form_tag_rules(edition: EditionLang) -> &'static [TagRule] {
+    fn build(include_multiword_construction: bool) -> Vec<TagRule> {
+        let mut tags: Vec<&str> = BLACKLISTED_FORM_TAGS.to_vec();
+        if include_multiword_construction {
+            tags.push("multiword-construction");
+        }
+        tags.into_iter()
+            .map(|tag| TagRule {
+                context: vec![tag.to_string()],
+                ..TagRule::default()
+            })
+            .collect()
+    }
+
+    static WITH_MULTIWORD_CONSTRUCTION: std::sync::OnceLock<Vec<TagRule>> =
+        std::sync::OnceLock::new();
+    static WITHOUT_MULTIWORD_CONSTRUCTION: std::sync::OnceLock<Vec<TagRule>> =
+        std::sync::OnceLock::new();
+
+    if edition == EditionLang::De {
+        WITHOUT_MULTIWORD_CONSTRUCTION.get_or_init(|| build(false))
+    } else {
+        WITH_MULTIWORD_CONSTRUCTION.get_or_init(|| build(true))
+    }
+}
+
+/// Whether a form's `tags` should be dropped during tidy, replacing the old direct
+/// `BLACKLISTED_FORM_TAGS`/`IDENTITY_FORM_TAGS` checks in [`WordEntry::non_trivial_forms`].
+///
+/// The blacklist half queries [`TagRule::eligible`] directly instead of rewriting-and-checking
+/// through [`apply_tag_rules`]: every rule in [`blacklisted_form_tag_rules`] only gates on
+/// `context`, so eligibility alone (no `input`/`output`, no mutation) is exactly equivalent to
+/// applying it. The identity half does not fit a rule: `context`/`filter` only test presence/absence
+/// of specific tags, but "every tag the form has is in `IDENTITY_FORM_TAGS`" is a bound on the
+/// *whole* set with an unbounded tag vocabulary on the other side, so it stays a plain subset check.
+///
+/// [`WordEntry`]: crate::models::kaikki::WordEntry
+pub fn is_blacklisted_or_identity_form(tags: &[Tag], edition: EditionLang) -> bool {
+    let is_blacklisted = blacklisted_form_tag_rules(edition)
+        .iter()
+        .any(|rule| rule.eligible(tags));
+
+    let is_identity = tags
+        .iter()
+        .all(|tag| IDENTITY_FORM_TAGS.contains(&tag.as_str()));
+
+    is_blacklisted || is_identity
+}
+
+/// Apply `rules` to `tags` to a monotonic fixpoint.
+///
+/// The scan is repeated until no rule fires. To stay terminating even for rules whose `output`
+/// does not consume their `input` (which would otherwise fire forever), each rule is applied at
+/// most once per tag list: the set of already-fired rule indices is tracked and skipped.
+pub fn apply_tag_rules(rules: &[TagRule], tags: &mut Vec<Tag>) {
+    let mut fired = vec![false; rules.len()];
+    loop {
+        let mut progressed = false;
+        for (i, rule) in rules.iter().enumerate() {
+            if fired[i] {
+                continue;
+            }
+            if rule.apply(tags) {
+                fired[i] = true;
+                progressed = true;
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+}
+
+/// A boolean query over a form's tag set, used to declaratively keep or drop expanded forms
+/// during tidy instead of relying only on [`BLACKLISTED_FORM_TAGS`]/[`IDENTITY_FORM_TAGS`].
+/// Driven by `ArgsOptions::form_filter`: `make_dict` drops any form matching the query before
+/// handing the entry to a `Dictionary`'s `preprocess`/`process`.
+///
+/// A leaf [`Tag`](TagFilterAst::Tag) is true when that tag is present in the form's tag set;
+/// [`And`](TagFilterAst::And)/[`Or`](TagFilterAst::Or)/[`Not`](TagFilterAst::Not) combine leaves.
+/// Parse one from a query string with [`TagFilterAst::parse`] (e.g.
+/// `obsolete OR (multiword-construction AND NOT present)`) and evaluate it with [`matches`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagFilterAst {
+    /// True when this tag is present in the form's tag set.
+    Tag(String),
+    /// True when every child is true (vacuously true when empty).
+    And(Vec<TagFilterAst>),
+    /// True when any child is true (vacuously false when empty).
+    Or(Vec<TagFilterAst>),
+    /// Logical negation of the child.
+    Not(Box<TagFilterAst>),
+}
+
+impl TagFilterAst {
+    /// Parse a query string into an AST.
+    ///
+    /// Grammar (lowest precedence first): `OR`, `AND`, `NOT`, then tags and parenthesized groups.
+    /// The `AND`/`OR`/`NOT` keywords are case-insensitive; every other bare word is a tag.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let tokens = tokenize(s)?;
+        let mut parser = FilterParser { tokens: &tokens, pos: 0 };
+        let ast = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(format!("unexpected trailing tokens in filter: {s:?}"));
+        }
+        Ok(ast)
+    }
+
+    /// Fold constant/empty branches and flatten nested `And`/`Or` of the same kind.
+    pub fn simplify(self) -> Self {
+        match self {
+            Self::Tag(_) => self,
+            Self::Not(inner) => match inner.simplify() {
+                // Double negation cancels out.
+                Self::Not(inner) => *inner,
+                other => Self::Not(Box::new(other)),
+            },
+            Self::And(children) => Self::flatten(children, true),
+            Self::Or(children) => Self::flatten(children, false),
+        }
+    }
+
+    fn flatten(children: Vec<TagFilterAst>, is_and: bool) -> Self {
+        let mut flat = Vec::new();
+        for child in children {
+            match child.simplify() {
+                Self::And(inner) if is_and => flat.extend(inner),
+                Self::Or(inner) if !is_and => flat.extend(inner),
+                other => flat.push(other),
+            }
+        }
+        // Collapse a single surviving branch; otherwise keep the (possibly empty) node.
+        if flat.len() == 1 {
+            flat.pop().unwrap()
+        } else if is_and {
+            Self::And(flat)
+        } else {
+            Self::Or(flat)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FilterToken {
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+    Tag(String),
+}
+
+fn tokenize(s: &str) -> Result<Vec<FilterToken>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(FilterToken::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(FilterToken::RParen);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.to_ascii_uppercase().as_str() {
+                    "AND" => FilterToken::And,
+                    "OR" => FilterToken::Or,
+                    "NOT" => FilterToken::Not,
+                    _ => FilterToken::Tag(word),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct FilterParser<'a> {
+    tokens: &'a [FilterToken],
+    pos: usize,
+}
+
+impl FilterParser<'_> {
+    fn peek(&self) -> Option<&FilterToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> Result<TagFilterAst, String> {
+        let mut terms = vec![self.parse_and()?];
+        while matches!(self.peek(), Some(FilterToken::Or)) {
+            self.pos += 1;
+            terms.push(self.parse_and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.pop().unwrap()
+        } else {
+            TagFilterAst::Or(terms)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<TagFilterAst, String> {
+        let mut factors = vec![self.parse_not()?];
+        while matches!(self.peek(), Some(FilterToken::And)) {
+            self.pos += 1;
+            factors.push(self.parse_not()?);
+        }
+        Ok(if factors.len() == 1 {
+            factors.pop().unwrap()
+        } else {
+            TagFilterAst::And(factors)
+        })
+    }
+
+    fn parse_not(&mut self) -> Result<TagFilterAst, String> {
+        if matches!(self.peek(), Some(FilterToken::Not)) {
+            self.pos += 1;
+            return Ok(TagFilterAst::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_atom()
+    }
+
+    fn parse_atom(&mut self) -> Result<TagFilterAst, String> {
+        match self.peek() {
+            Some(FilterToken::Tag(tag)) => {
+                let tag = tag.clone();
+                self.pos += 1;
+                Ok(TagFilterAst::Tag(tag))
+            }
+            Some(FilterToken::LParen) => {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                if !matches!(self.peek(), Some(FilterToken::RParen)) {
+                    return Err("expected closing ')'".to_string());
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            other => Err(format!("unexpected token in filter: {other:?}")),
+        }
+    }
+}
+
+/// Evaluate `ast` against a form's `tags`, treating the tags as a set of present leaves.
+pub fn matches(ast: &TagFilterAst, tags: &[Tag]) -> bool {
+    match ast {
+        TagFilterAst::Tag(tag) => tags.iter().any(|t| t == tag),
+        TagFilterAst::And(children) => children.iter().all(|c| matches(c, tags)),
+        TagFilterAst::Or(children) => children.iter().any(|c| matches(c, tags)),
+        TagFilterAst::Not(inner) => !matches(inner, tags),
+    }
+}
+
 /// Return a Vec<TagInformation> from `tag_bank_terms` that fits the yomitan tag schema.
 pub fn get_tag_bank_as_tag_info() -> Vec<TagInformation> {
     TAG_BANK.iter().map(TagInformation::new).collect()
@@ -205,6 +758,84 @@ pub fn find_tag_in_bank(tag: &str) -> Option<TagInformation> {
     })
 }
 
+/// Jaro string similarity in `[0, 1]`.
+///
+/// Counts characters that match within a window of `floor(max(|a|, |b|) / 2) - 1` and the number
+/// of transpositions `t`, giving `(m/|a| + m/|b| + (m - t)/m) / 3`, or `0` when `m = 0`.
+fn jaro(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+
+    let mut matches = 0;
+    for (i, &ca) in a.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(b.len());
+        for j in lo..hi {
+            if !b_matched[j] && ca == b[j] {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0;
+    let mut k = 0;
+    for (i, &ca) in a.iter().enumerate() {
+        if a_matched[i] {
+            while !b_matched[k] {
+                k += 1;
+            }
+            if ca != b[k] {
+                transpositions += 1;
+            }
+            k += 1;
+        }
+    }
+
+    let matches = matches as f64;
+    let t = transpositions as f64 / 2.0;
+    (matches / a.len() as f64 + matches / b.len() as f64 + (matches - t) / matches) / 3.0
+}
+
+/// Suggest the closest known tag for an unrecognized one, using Jaro similarity.
+///
+/// Scores `tag` against every normalized long tag in `TAG_BANK`, keeps candidates above `0.7`,
+/// and returns the best match (or `None` when nothing is close enough).
+pub fn suggest_tag(tag: &str) -> Option<TagInformation> {
+    TAG_BANK
+        .iter()
+        .filter_map(|entry| {
+            let score = jaro(tag, entry.3[0]);
+            (score > 0.7).then_some((score, entry))
+        })
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, entry)| TagInformation::new(entry))
+}
+
+/// Look up a tag, falling back to the nearest [`suggest_tag`] match in lenient mode.
+///
+/// Strict mode keeps the original drop-on-miss behavior; lenient mode remaps spelling drift to the
+/// closest known tag instead.
+pub fn find_tag_in_bank_lenient(tag: &str) -> Option<TagInformation> {
+    find_tag_in_bank(tag).or_else(|| suggest_tag(tag))
+}
+
 /// Look for the short form in POSES (`tag_bank_terms.json` with category "partOfSpeech") and
 /// return the short form if any.
 pub fn find_short_pos(pos: &str) -> Option<&'static str> {
@@ -297,6 +928,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merge_tags_by_axes_number() {
+        let mut received = to_string_vec(&["singular accusative", "plural accusative"]);
+        merge_tags_by_axes(&mut received, &[MergeAxis::Number]);
+        assert_eq!(received, to_string_vec(&["accusative singular/plural"]));
+    }
+
+    #[test]
+    fn merge_tags_by_axes_gender_shares_nothing() {
+        let mut received = to_string_vec(&["masculine nominative", "feminine nominative"]);
+        merge_tags_by_axes(&mut received, &[MergeAxis::Gender]);
+        assert_eq!(received, to_string_vec(&["nominative masculine/feminine"]));
+    }
+
     #[test]
     fn remove_redundant_tags1() {
         let mut received = to_string_vec(&["foo", "bar", "foo bar", "foo bar zee"]);
@@ -341,8 +986,164 @@ mod tests {
     }
 
     #[test]
-    fn tags_subsets() {
-        assert!(tags_are_subset("foo bar", "bar foo baz"));
-        assert!(!tags_are_subset("foo qux", "foo bar baz"));
+    fn jaro_similarity() {
+        assert_eq!(jaro("", ""), 1.0);
+        assert_eq!(jaro("abc", ""), 0.0);
+        assert_eq!(jaro("abc", "abc"), 1.0);
+        // Classic reference pair: jaro("martha", "marhta") == 0.944...
+        assert!((jaro("martha", "marhta") - 0.944_444).abs() < 1e-5);
+        // A single transposition should still score well above the 0.7 cutoff.
+        assert!(jaro("plural", "plrual") > 0.7);
+    }
+
+    #[test]
+    fn normalize_tags_expands_compact_codes() {
+        let mut tags = to_string_vec(&["f_acc", "plural"]);
+        normalize_tags(EditionLang::Ru, &mut tags);
+        assert_eq!(tags, to_string_vec(&["feminine", "accusative", "plural"]));
+    }
+
+    #[test]
+    fn normalize_tags_noop_for_canonical_editions() {
+        let mut tags = to_string_vec(&["feminine", "accusative"]);
+        normalize_tags(EditionLang::En, &mut tags);
+        assert_eq!(tags, to_string_vec(&["feminine", "accusative"]));
+    }
+
+    fn rule(context: &[&str], filter: &[&str], input: &[&str], output: &[&str]) -> TagRule {
+        TagRule {
+            context: to_string_vec(context),
+            filter: to_string_vec(filter),
+            input: to_string_vec(input),
+            output: to_string_vec(output),
+            optional: false,
+        }
+    }
+
+    #[test]
+    fn apply_tag_rules_context_and_filter() {
+        // Fires only when `plural` is present and `obsolete` is absent.
+        let rules = [rule(&["plural"], &["obsolete"], &["dative"], &["oblique"])];
+
+        let mut fires = to_string_vec(&["plural", "dative"]);
+        apply_tag_rules(&rules, &mut fires);
+        assert_eq!(fires, to_string_vec(&["plural", "oblique"]));
+
+        let mut blocked = to_string_vec(&["plural", "dative", "obsolete"]);
+        apply_tag_rules(&rules, &mut blocked);
+        assert_eq!(blocked, to_string_vec(&["plural", "dative", "obsolete"]));
+    }
+
+    #[test]
+    fn apply_tag_rules_fixpoint_chains() {
+        let rules = [
+            rule(&[], &[], &["a"], &["b"]),
+            rule(&[], &[], &["b"], &["c"]),
+        ];
+        let mut tags = to_string_vec(&["a"]);
+        apply_tag_rules(&rules, &mut tags);
+        assert_eq!(tags, to_string_vec(&["c"]));
+    }
+
+    // A rule whose output does not consume its input must not loop forever.
+    #[test]
+    fn apply_tag_rules_non_consuming_terminates() {
+        let rules = [rule(&["singular"], &[], &[], &["number-marked"])];
+        let mut tags = to_string_vec(&["singular"]);
+        apply_tag_rules(&rules, &mut tags);
+        assert_eq!(tags, to_string_vec(&["singular", "number-marked"]));
+    }
+
+    #[test]
+    fn blacklisted_form_tags_are_edition_independent() {
+        assert!(is_blacklisted_or_identity_form(
+            &to_string_vec(&["romanization"]),
+            EditionLang::En
+        ));
+        assert!(is_blacklisted_or_identity_form(
+            &to_string_vec(&["romanization"]),
+            EditionLang::De
+        ));
+    }
+
+    #[test]
+    fn identity_form_tags_are_dropped_on_any_edition() {
+        assert!(is_blacklisted_or_identity_form(
+            &to_string_vec(&["singular", "nominative"]),
+            EditionLang::En
+        ));
+        assert!(!is_blacklisted_or_identity_form(
+            &to_string_vec(&["singular", "plural"]),
+            EditionLang::En
+        ));
+    }
+
+    // `multiword-construction` is noise for fr-en ("present indicative of avoir + past
+    // participle") but a legitimate German form ("werde gepflogen haben" for "pflegen"), so it is
+    // only blacklisted outside German editions.
+    #[test]
+    fn multiword_construction_is_blacklisted_except_in_german() {
+        let tags = to_string_vec(&["indicative", "multiword-construction", "present"]);
+        assert!(is_blacklisted_or_identity_form(&tags, EditionLang::Fr));
+        assert!(!is_blacklisted_or_identity_form(&tags, EditionLang::De));
+    }
+
+    #[test]
+    fn tag_filter_parse_and_match() {
+        let ast = TagFilterAst::parse("obsolete OR (multiword-construction AND NOT present)")
+            .expect("valid filter");
+        assert!(matches(&ast, &to_string_vec(&["obsolete", "singular"])));
+        assert!(matches(
+            &ast,
+            &to_string_vec(&["multiword-construction", "past"])
+        ));
+        assert!(!matches(
+            &ast,
+            &to_string_vec(&["multiword-construction", "present"])
+        ));
+        assert!(!matches(&ast, &to_string_vec(&["plural"])));
+    }
+
+    #[test]
+    fn tag_filter_simplify_flattens_and_cancels() {
+        let ast = TagFilterAst::And(vec![
+            TagFilterAst::And(vec![TagFilterAst::Tag("a".into())]),
+            TagFilterAst::Not(Box::new(TagFilterAst::Not(Box::new(TagFilterAst::Tag(
+                "b".into(),
+            ))))),
+        ]);
+        assert_eq!(
+            ast.simplify(),
+            TagFilterAst::And(vec![
+                TagFilterAst::Tag("a".into()),
+                TagFilterAst::Tag("b".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn canonicalize_usage_labels_folds_synonyms() {
+        let mut tags = to_string_vec(&["figuratively", "low colloquial", "plural"]);
+        canonicalize_usage_labels(&mut tags);
+        // Synonyms fold, the unrecognized grammatical tag passes through untouched.
+        assert_eq!(
+            tags,
+            to_string_vec(&["figurative", "low-colloquial", "plural"])
+        );
+    }
+
+    // A form carrying many tags must still collapse the same way the pairwise version did.
+    #[test]
+    fn remove_redundant_tags_many() {
+        let mut received = to_string_vec(&[
+            "singular",
+            "plural",
+            "nominative singular",
+            "nominative plural",
+            "accusative nominative singular",
+        ]);
+        let expected = to_string_vec(&["accusative nominative singular", "nominative plural"]);
+        remove_redundant_tags(&mut received);
+        assert_eq!(received, expected);
     }
 }
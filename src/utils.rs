@@ -6,6 +6,7 @@ use crate::lang::{EditionLang, Lang};
 
 pub const SKIP_C: &str = "⏭";
 pub const CHECK_C: &str = "✓";
+pub const FAIL_C: &str = "✗";
 
 fn size(path: &Path) -> std::io::Result<u64> {
     let md = fs::metadata(path)?;
@@ -23,7 +24,7 @@ fn size(path: &Path) -> std::io::Result<u64> {
     }
 }
 
-fn human_size(size_bytes: f64) -> String {
+pub(crate) fn human_size(size_bytes: f64) -> String {
     let mut size = size_bytes;
     for unit in ["B", "KB", "MB"] {
         if size < 1024.0 {
@@ -34,7 +35,7 @@ fn human_size(size_bytes: f64) -> String {
     format!("{:.2} GB", size)
 }
 
-fn get_file_size_human(path: &Path) -> Result<String> {
+pub(crate) fn get_file_size_human(path: &Path) -> Result<String> {
     Ok(human_size(size(path)? as f64))
 }
 
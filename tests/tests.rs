@@ -1,4 +1,6 @@
-use kty::cli::{DictName, GlossaryArgs, GlossaryLangs, IpaArgs, MainArgs, MainLangs, Options};
+use kty::cli::{
+    DictName, GlossaryArgs, GlossaryLangs, IpaArgs, IrFormat, MainArgs, MainLangs, Options,
+};
 use kty::dict::{DGlossary, DIpa, DMain};
 use kty::lang::{Edition, Lang};
 use kty::make_dict;
@@ -181,6 +183,56 @@ fn snapshot() {
     cleanup(&fixture_dir.join("dict"));
 }
 
+/// `--reuse-ir` should reload the `--save-temps --ir-format cbor` checkpoint instead of re-reading
+/// the raw JSONL extract. Prove it actually took that path (not just that the two runs agree) by
+/// moving the raw extract out of the way before the second run: if `--reuse-ir` silently fell back
+/// to a full rebuild, it would have nothing left to read and fail.
+#[test]
+fn reuse_ir_skips_raw_reload() -> Result<()> {
+    setup_tracing_test();
+
+    let fixture_dir = PathBuf::from("tests");
+    let fixture_input_dir = fixture_dir.join("kaikki");
+
+    let (source, target, extract_path) = fs::read_dir(&fixture_input_dir)
+        .unwrap()
+        .flatten()
+        .find_map(|entry| {
+            let path = entry.path();
+            let fname = path.file_name()?.to_str()?;
+            let base = fname.strip_suffix("-extract.jsonl")?;
+            let (source, target) = base.split_once('-')?;
+            let source = source.parse::<Lang>().ok()?;
+            let target: Edition = target.parse::<Lang>().ok()?.try_into().ok()?;
+            Some((source, target, path))
+        })
+        .expect("fixture tree has at least one main-dictionary case");
+
+    let mut args = fixture_main_args(source, target, &fixture_dir);
+    args.options.ir_format = IrFormat::Cbor;
+
+    let pm = PathManager::try_from(args.clone()).unwrap();
+    delete_previous_output(&pm)?;
+    make_dict(DMain, args.clone())?;
+
+    let checkpoint = pm.dir_tidy().join("tidy.cbor");
+    assert!(
+        checkpoint.exists(),
+        "expected a binary IR checkpoint at {checkpoint:?}"
+    );
+
+    let moved_extract = extract_path.with_extension("jsonl.moved");
+    fs::rename(&extract_path, &moved_extract)?;
+
+    args.options.reuse_ir = true;
+    let result = make_dict(DMain, args);
+
+    // Restore the fixture before returning the second run's result, even when that run failed.
+    fs::rename(&moved_extract, &extract_path)?;
+
+    result
+}
+
 /// Delete generated artifacts from previous tests runs, if any
 fn delete_previous_output(pm: &PathManager) -> Result<()> {
     let pathdir_dict_temp = pm.dir_temp_dict();